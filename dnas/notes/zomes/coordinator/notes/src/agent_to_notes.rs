@@ -0,0 +1,12 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+#[hdk_extern]
+pub fn get_notes_for_agent(agent: AgentPubKey) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToNotes)?.build())
+}
+
+#[hdk_extern]
+pub fn get_my_notes() -> ExternResult<Vec<Link>> {
+    get_notes_for_agent(agent_info()?.agent_initial_pubkey)
+}
@@ -0,0 +1,111 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttachFileInput {
+    pub original_note_hash: ActionHash,
+    pub name: String,
+    pub mime_type: String,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+// Splits the file in chunks, commits them and a manifest describing the file, and links it to the note
+#[hdk_extern]
+pub fn attach_file(input: AttachFileInput) -> ExternResult<Record> {
+    let mut chunks: Vec<EntryHash> = vec![];
+    for bytes in input.bytes.chunks(MAX_CHUNK_BYTES) {
+        let chunk = AttachmentChunk {
+            bytes: bytes.to_vec(),
+        };
+        chunks.push(hash_entry(&chunk)?);
+        create_entry(&EntryTypes::AttachmentChunk(chunk))?;
+    }
+    let manifest = AttachmentManifest {
+        name: input.name,
+        mime_type: input.mime_type,
+        size: input.bytes.len() as u64,
+        sha256: hash_sha256(input.bytes)?,
+        chunks,
+    };
+    let manifest_hash = create_entry(&EntryTypes::AttachmentManifest(manifest))?;
+    create_link(
+        input.original_note_hash,
+        manifest_hash.clone(),
+        LinkTypes::NoteToAttachments,
+        (),
+    )?;
+    let record = get(manifest_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created AttachmentManifest".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Attachment {
+    pub manifest: AttachmentManifest,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+// Fetches the manifest and reassembles the file from its chunks
+#[hdk_extern]
+pub fn get_attachment(manifest_hash: ActionHash) -> ExternResult<Option<Attachment>> {
+    let Some(record) = get(manifest_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    let manifest: AttachmentManifest = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Malformed AttachmentManifest".to_string()
+        )))?;
+    let get_input = manifest
+        .chunks
+        .iter()
+        .map(|chunk_hash| GetInput::new(chunk_hash.clone().into(), GetOptions::default()))
+        .collect();
+    let chunk_records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    let mut bytes: Vec<u8> = Vec::with_capacity(manifest.size as usize);
+    for chunk_record in chunk_records {
+        let chunk: AttachmentChunk = chunk_record
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "Could not find a chunk of the attachment".to_string()
+            )))?
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "Malformed AttachmentChunk".to_string()
+            )))?;
+        bytes.extend(chunk.bytes);
+    }
+    Ok(Some(Attachment { manifest, bytes }))
+}
+
+#[hdk_extern]
+pub fn list_attachments(original_note_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(original_note_hash, LinkTypes::NoteToAttachments)?.build(),
+    )
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoveAttachmentInput {
+    pub original_note_hash: ActionHash,
+    pub manifest_hash: ActionHash,
+}
+
+// Unlinks the attachment from the note and deletes its manifest
+// Chunks are content-addressed and may be shared with other attachments, so they are left in place
+#[hdk_extern]
+pub fn remove_attachment(input: RemoveAttachmentInput) -> ExternResult<ActionHash> {
+    let links = list_attachments(input.original_note_hash)?;
+    for link in links {
+        if link.target.into_action_hash().as_ref() == Some(&input.manifest_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    delete_entry(input.manifest_hash)
+}
@@ -0,0 +1,83 @@
+use hdk::prelude::*;
+
+use crate::list_notes::{get_note_list_items, NoteListItem};
+
+pub const PROFILE_ZOME: &str = "profile";
+
+// The public part of a profile, read from the profile zome's entry without depending on its integrity crate
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+pub struct AuthorProfile {
+    pub nickname: String,
+    #[serde(default)]
+    pub avatar: Option<EntryHash>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteWithAuthor {
+    pub note: NoteListItem,
+    // None when the author has not created a profile yet
+    pub author_profile: Option<AuthorProfile>,
+}
+
+// Asks the profile zome of this cell for the latest profile of the agent
+pub fn get_author_profile(agent: AgentPubKey) -> ExternResult<Option<AuthorProfile>> {
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from(PROFILE_ZOME),
+        FunctionName::from("get_agent_profile"),
+        None,
+        agent,
+    )?;
+    let record: Option<Record> = match response {
+        ZomeCallResponse::Ok(result) => result.decode().map_err(|e| wasm_error!(e))?,
+        other => {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "Could not get the author's profile: {other:?}"
+            ))))
+        }
+    };
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    record
+        .entry()
+        .to_app_option::<AuthorProfile>()
+        .map_err(|e| wasm_error!(e))
+}
+
+#[hdk_extern]
+pub fn get_note_with_author(
+    original_note_hash: ActionHash,
+) -> ExternResult<Option<NoteWithAuthor>> {
+    Ok(get_notes_with_authors(vec![original_note_hash])?.pop())
+}
+
+// Hydrates a page of notes, resolving each distinct author's profile only once
+#[hdk_extern]
+pub fn get_notes_with_authors(
+    original_note_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<NoteWithAuthor>> {
+    let notes = get_note_list_items(original_note_hashes)?;
+    let mut profiles: Vec<(AgentPubKey, Option<AuthorProfile>)> = vec![];
+    for note in notes.iter() {
+        if !profiles.iter().any(|(agent, _)| *agent == note.author) {
+            profiles.push((
+                note.author.clone(),
+                get_author_profile(note.author.clone())?,
+            ));
+        }
+    }
+    Ok(notes
+        .into_iter()
+        .map(|note| {
+            let author_profile = profiles
+                .iter()
+                .find(|(agent, _)| *agent == note.author)
+                .and_then(|(_, profile)| profile.clone());
+            NoteWithAuthor {
+                note,
+                author_profile,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,60 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::note::{get_latest_note, update_note, UpdateNoteInput};
+
+// Fetches the latest revision of a note along with its action hash
+fn get_latest_note_revision(original_note_hash: ActionHash) -> ExternResult<(ActionHash, Note)> {
+    let record = get_latest_note(original_note_hash)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Could not find the Note".to_string()
+        )))?
+        .record;
+    let note: Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    Ok((record.action_address().clone(), note))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteEditorInput {
+    pub original_note_hash: ActionHash,
+    pub editor: AgentPubKey,
+}
+
+// Pushes a revision of the note that lets the given agent edit it
+#[hdk_extern]
+pub fn add_editor(input: NoteEditorInput) -> ExternResult<Record> {
+    let (previous_note_hash, mut note) =
+        get_latest_note_revision(input.original_note_hash.clone())?;
+    if !note.editors.contains(&input.editor) {
+        note.editors.push(input.editor);
+    }
+    update_note(UpdateNoteInput {
+        original_note_hash: input.original_note_hash,
+        previous_note_hash,
+        updated_note: note,
+    })
+}
+
+// Pushes a revision of the note that no longer lets the given agent edit it
+#[hdk_extern]
+pub fn remove_editor(input: NoteEditorInput) -> ExternResult<Record> {
+    let (previous_note_hash, mut note) =
+        get_latest_note_revision(input.original_note_hash.clone())?;
+    note.editors.retain(|editor| *editor != input.editor);
+    update_note(UpdateNoteInput {
+        original_note_hash: input.original_note_hash,
+        previous_note_hash,
+        updated_note: note,
+    })
+}
+
+#[hdk_extern]
+pub fn get_note_editors(original_note_hash: ActionHash) -> ExternResult<Vec<AgentPubKey>> {
+    Ok(get_latest_note_revision(original_note_hash)?.1.editors)
+}
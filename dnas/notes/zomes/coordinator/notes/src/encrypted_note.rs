@@ -0,0 +1,243 @@
+use hdk::prelude::nonce::XSalsa20Poly1305Nonce;
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::note::latest_note_hash;
+
+// Looks up the x25519 key an agent has published for content keys to be sealed to
+pub fn get_encryption_key(agent: AgentPubKey) -> ExternResult<Option<X25519PubKey>> {
+    let links =
+        get_links(GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToEncryptionKey)?.build())?;
+    let Some(link) = links.into_iter().min_by_key(|link| link.timestamp) else {
+        return Ok(None);
+    };
+    X25519PubKey::try_from(link.tag.into_inner())
+        .map(Some)
+        .map_err(|e| {
+            wasm_error!(WasmErrorInner::Guest(format!(
+                "Malformed encryption key: {e:?}"
+            )))
+        })
+}
+
+// Generates and publishes the local agent's x25519 key the first time it is needed
+// The secret half never leaves lair
+pub fn ensure_my_encryption_key() -> ExternResult<X25519PubKey> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    if let Some(key) = get_encryption_key(my_agent_pub_key.clone())? {
+        return Ok(key);
+    }
+    let key = create_x25519_keypair()?;
+    create_link(
+        my_agent_pub_key.clone(),
+        my_agent_pub_key,
+        LinkTypes::AgentToEncryptionKey,
+        LinkTag::new(key.as_ref()),
+    )?;
+    Ok(key)
+}
+
+// Agents must publish their encryption key before notes can be shared with them
+#[hdk_extern]
+pub fn publish_encryption_key() -> ExternResult<X25519PubKey> {
+    ensure_my_encryption_key()
+}
+
+// Boxes the content key from the local agent to the recipient's published encryption key
+pub fn seal_content_key(
+    key_ref: XSalsa20Poly1305KeyRef,
+    sender_key: X25519PubKey,
+    recipient: AgentPubKey,
+) -> ExternResult<KeyEnvelope> {
+    let recipient_key =
+        get_encryption_key(recipient.clone())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+            format!("Agent {recipient} has not published an encryption key yet")
+        )))?;
+    let sealed = x_salsa20_poly1305_shared_secret_export(sender_key, recipient_key, key_ref)?;
+    Ok(KeyEnvelope {
+        recipient,
+        sender_key,
+        nonce: sealed.as_nonce_ref().as_ref().to_vec(),
+        sealed_key: sealed.as_encrypted_data_ref().to_vec(),
+    })
+}
+
+// Recovers the content key of an encrypted note from the envelope sealed to the local agent
+pub fn open_content_key(encrypted_note: &EncryptedNote) -> ExternResult<XSalsa20Poly1305KeyRef> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    let envelope = encrypted_note
+        .envelopes
+        .iter()
+        .find(|envelope| envelope.recipient == my_agent_pub_key)
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "This note has not been shared with you".to_string()
+        )))?;
+    let my_key = get_encryption_key(my_agent_pub_key)?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("You have not published an encryption key yet".to_string())
+    ))?;
+    let sealed =
+        XSalsa20Poly1305EncryptedData::new(to_nonce(&envelope.nonce)?, envelope.sealed_key.clone());
+    x_salsa20_poly1305_shared_secret_ingest(my_key, envelope.sender_key, sealed, None)
+}
+
+fn to_nonce(bytes: &[u8]) -> ExternResult<XSalsa20Poly1305Nonce> {
+    XSalsa20Poly1305Nonce::try_from(bytes)
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(format!("Malformed nonce: {e:?}"))))
+}
+
+// Seals a fresh content key to every recipient and encrypts the note with it
+pub fn encrypt_note(note: &Note, recipients: Vec<AgentPubKey>) -> ExternResult<EncryptedNote> {
+    let my_key = ensure_my_encryption_key()?;
+    let key_ref = x_salsa20_poly1305_shared_secret_create_random(None)?;
+    let plaintext = SerializedBytes::try_from(note.clone()).map_err(|e| wasm_error!(e))?;
+    let encrypted = x_salsa20_poly1305_encrypt(key_ref.clone(), plaintext.bytes().to_vec().into())?;
+    let envelopes = recipients
+        .into_iter()
+        .map(|recipient| seal_content_key(key_ref.clone(), my_key, recipient))
+        .collect::<ExternResult<Vec<KeyEnvelope>>>()?;
+    Ok(EncryptedNote {
+        nonce: encrypted.as_nonce_ref().as_ref().to_vec(),
+        ciphertext: encrypted.as_encrypted_data_ref().to_vec(),
+        envelopes,
+    })
+}
+
+pub fn decrypt_note(encrypted_note: &EncryptedNote) -> ExternResult<Note> {
+    let key_ref = open_content_key(encrypted_note)?;
+    let encrypted = XSalsa20Poly1305EncryptedData::new(
+        to_nonce(&encrypted_note.nonce)?,
+        encrypted_note.ciphertext.clone(),
+    );
+    let plaintext = x_salsa20_poly1305_decrypt(key_ref, encrypted)?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not decrypt the note".to_string())
+    ))?;
+    Note::try_from(SerializedBytes::from(UnsafeBytes::from(
+        plaintext.as_ref().to_vec(),
+    )))
+    .map_err(|e| wasm_error!(e))
+}
+
+#[hdk_extern]
+pub fn create_encrypted_note(note: Note) -> ExternResult<Record> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    let encrypted_note = encrypt_note(&note, vec![my_agent_pub_key.clone()])?;
+    let encrypted_note_hash = create_entry(&EntryTypes::EncryptedNote(encrypted_note))?;
+    create_link(
+        my_agent_pub_key,
+        encrypted_note_hash.clone(),
+        LinkTypes::AgentToEncryptedNotes,
+        (),
+    )?;
+    let record = get(encrypted_note_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created EncryptedNote".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_latest_encrypted_note(
+    original_encrypted_note_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let latest_hash = latest_encrypted_note_hash(original_encrypted_note_hash)?;
+    get(latest_hash, GetOptions::default())
+}
+
+// Decrypts the latest revision of an encrypted note with the local agent's key
+#[hdk_extern]
+pub fn decrypt_encrypted_note(
+    original_encrypted_note_hash: ActionHash,
+) -> ExternResult<Option<Note>> {
+    let Some(record) = get_latest_encrypted_note(original_encrypted_note_hash)? else {
+        return Ok(None);
+    };
+    let encrypted_note: EncryptedNote = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    decrypt_note(&encrypted_note).map(Some)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateEncryptedNoteInput {
+    pub original_encrypted_note_hash: ActionHash,
+    pub previous_encrypted_note_hash: ActionHash,
+    pub updated_note: Note,
+}
+
+// Every update is encrypted under a fresh content key, sealed to the same recipients as before
+// so that agents whose share was revoked cannot read revisions made after the revocation
+#[hdk_extern]
+pub fn update_encrypted_note(input: UpdateEncryptedNoteInput) -> ExternResult<Record> {
+    let previous_encrypted_note = get_encrypted_note(input.previous_encrypted_note_hash.clone())?;
+    let recipients = previous_encrypted_note
+        .envelopes
+        .into_iter()
+        .map(|envelope| envelope.recipient)
+        .collect();
+    let encrypted_note = encrypt_note(&input.updated_note, recipients)?;
+    commit_encrypted_note_revision(
+        input.original_encrypted_note_hash,
+        input.previous_encrypted_note_hash,
+        encrypted_note,
+    )
+}
+
+// Fetches the encrypted note committed by the given action
+pub fn get_encrypted_note(encrypted_note_hash: ActionHash) -> ExternResult<EncryptedNote> {
+    let record = get(encrypted_note_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the EncryptedNote".to_string())
+    ))?;
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))
+}
+
+// Resolves the hash of the latest revision of an encrypted note
+pub fn latest_encrypted_note_hash(
+    original_encrypted_note_hash: ActionHash,
+) -> ExternResult<ActionHash> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            original_encrypted_note_hash.clone(),
+            LinkTypes::EncryptedNoteUpdates,
+        )?
+        .build(),
+    )?;
+    latest_note_hash(original_encrypted_note_hash, links)
+}
+
+pub fn commit_encrypted_note_revision(
+    original_encrypted_note_hash: ActionHash,
+    previous_encrypted_note_hash: ActionHash,
+    encrypted_note: EncryptedNote,
+) -> ExternResult<Record> {
+    let updated_hash = update_entry(previous_encrypted_note_hash, &encrypted_note)?;
+    create_link(
+        original_encrypted_note_hash,
+        updated_hash.clone(),
+        LinkTypes::EncryptedNoteUpdates,
+        (),
+    )?;
+    let record = get(updated_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly updated EncryptedNote".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_my_encrypted_notes() -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(
+            agent_info()?.agent_initial_pubkey,
+            LinkTypes::AgentToEncryptedNotes,
+        )?
+        .build(),
+    )
+}
@@ -0,0 +1,187 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::note::{commit_note_update, get_note, UpdateNoteInput};
+
+// The revisions of a note that no other revision updates or merges, newest first
+// Only revisions and merges written by the owner or by editors of the owner's newest revision count: validation
+// checks editors against the owner's chain as the editor saw it, which may predate their removal
+pub fn note_heads(original_note_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    Ok(notes_heads(vec![original_note_hash])?
+        .pop()
+        .flatten()
+        .map(|note_heads| note_heads.heads)
+        .unwrap_or_default())
+}
+
+pub struct NoteHeads {
+    pub original_record: Record,
+    // Newest first, so the first one is the latest revision of the note
+    pub heads: Vec<Record>,
+}
+
+// The heads of each note with one batched get_links and two batched gets, None for notes that cannot be found
+pub fn notes_heads(original_note_hashes: Vec<ActionHash>) -> ExternResult<Vec<Option<NoteHeads>>> {
+    let get_input: Vec<GetInput> = original_note_hashes
+        .iter()
+        .map(|hash| GetInput::new(hash.clone().into(), GetOptions::default()))
+        .collect();
+    let original_records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    let get_links_input = original_note_hashes
+        .into_iter()
+        .map(|hash| Ok(GetLinksInputBuilder::try_new(hash, LinkTypes::NoteUpdates)?.build()))
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let note_updates_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    let mut merges: Vec<Vec<(AgentPubKey, MergedHeads)>> = vec![];
+    let mut get_input: Vec<GetInput> = vec![];
+    for links in note_updates_links {
+        let mut note_merges = vec![];
+        for link in links {
+            note_merges.push((link.author, MergedHeads::from_tag(link.tag)?));
+            let update_hash =
+                link.target
+                    .into_action_hash()
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(
+                        "No action hash associated with link".to_string()
+                    )))?;
+            get_input.push(GetInput::new(update_hash.into(), GetOptions::default()));
+        }
+        merges.push(note_merges);
+    }
+    let mut updates = HDK.with(|hdk| hdk.borrow().get(get_input))?.into_iter();
+    let mut notes_heads = vec![];
+    for (original_record, note_merges) in original_records.into_iter().zip(merges) {
+        let revisions: Vec<Record> = updates.by_ref().take(note_merges.len()).flatten().collect();
+        let Some(original_record) = original_record else {
+            notes_heads.push(None);
+            continue;
+        };
+        notes_heads.push(Some(NoteHeads {
+            heads: heads_among(original_record.clone(), note_merges, revisions)?,
+            original_record,
+        }));
+    }
+    Ok(notes_heads)
+}
+
+fn heads_among(
+    original_record: Record,
+    merges: Vec<(AgentPubKey, MergedHeads)>,
+    mut revisions: Vec<Record>,
+) -> ExternResult<Vec<Record>> {
+    let owner = original_record.action().author().clone();
+    revisions.push(original_record);
+    let allowed_editors = current_editors(&owner, &revisions)?;
+    let is_allowed = |author: &AgentPubKey| *author == owner || allowed_editors.contains(author);
+    let parents: HashMap<ActionHash, ActionHash> = revisions
+        .iter()
+        .filter_map(|record| match record.action() {
+            Action::Update(update) => Some((
+                record.action_address().clone(),
+                update.original_action_address.clone(),
+            )),
+            _ => None,
+        })
+        .collect();
+    // Everything an allowed revision or merge was built on is superseded, including the revisions
+    // of editors who have since been removed
+    let mut superseded: HashSet<ActionHash> = HashSet::new();
+    let mut supersede = |hash: &ActionHash| {
+        let mut next = Some(hash.clone());
+        while let Some(hash) = next {
+            if !superseded.insert(hash.clone()) {
+                break;
+            }
+            next = parents.get(&hash).cloned();
+        }
+    };
+    for record in revisions
+        .iter()
+        .filter(|record| is_allowed(record.action().author()))
+    {
+        if let Some(parent) = parents.get(record.action_address()) {
+            supersede(parent);
+        }
+    }
+    for (author, merged_heads) in merges {
+        if is_allowed(&author) {
+            merged_heads.0.iter().for_each(&mut supersede);
+        }
+    }
+    let mut heads: Vec<Record> = revisions
+        .into_iter()
+        .filter(|record| is_allowed(record.action().author()))
+        .filter(|record| !superseded.contains(record.action_address()))
+        .collect();
+    heads.sort_by_key(|record| Reverse(record.action().timestamp()));
+    Ok(heads)
+}
+
+// The editors listed by the newest revision of the note written by its owner
+fn current_editors(owner: &AgentPubKey, revisions: &[Record]) -> ExternResult<Vec<AgentPubKey>> {
+    let Some(newest_owner_revision) = revisions
+        .iter()
+        .filter(|record| record.action().author() == owner)
+        .max_by_key(|record| record.action().timestamp())
+    else {
+        return Ok(vec![]);
+    };
+    let note: Option<Note> = newest_owner_revision
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?;
+    Ok(note.map(|note| note.editors).unwrap_or_default())
+}
+
+#[hdk_extern]
+pub fn get_note_heads(original_note_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    note_heads(original_note_hash)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MergeNoteInput {
+    pub original_note_hash: ActionHash,
+    pub heads: Vec<ActionHash>,
+    pub merged_note: Note,
+}
+
+// Records a single update of the first head which also supersedes all the other given heads
+#[hdk_extern]
+pub fn merge_note(input: MergeNoteInput) -> ExternResult<Record> {
+    let current_heads: Vec<ActionHash> = note_heads(input.original_note_hash.clone())?
+        .into_iter()
+        .map(|record| record.action_address().clone())
+        .collect();
+    let mut heads = input.heads.into_iter();
+    let Some(previous_note_hash) = heads.next() else {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "At least one head is needed to merge a note".to_string()
+        )));
+    };
+    let mut merged_heads: Vec<ActionHash> = vec![];
+    for head in heads {
+        if head != previous_note_hash && !merged_heads.contains(&head) {
+            merged_heads.push(head);
+        }
+    }
+    let mut previous_notes: Vec<Note> = vec![];
+    for head in std::iter::once(&previous_note_hash).chain(merged_heads.iter()) {
+        if !current_heads.contains(head) {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "{head} is not a head of the note"
+            ))));
+        }
+        previous_notes.push(get_note(head.clone())?);
+    }
+    commit_note_update(
+        UpdateNoteInput {
+            original_note_hash: input.original_note_hash,
+            previous_note_hash,
+            updated_note: input.merged_note,
+        },
+        &previous_notes,
+        merged_heads,
+    )
+}
@@ -1,11 +1,36 @@
+pub mod agent_to_notes;
+pub mod attachments;
+pub mod authors;
+pub mod editors;
+pub mod encrypted_note;
+pub mod heads;
 pub mod list_notes;
 pub mod note;
+pub mod notebook;
+pub mod references;
+pub mod remote_signals;
+pub mod search;
+pub mod shared_with;
+pub mod subscriptions;
+pub mod tags;
+pub mod time_index;
+pub mod trash;
 use hdk::prelude::*;
 use notes_integrity::*;
 
+use crate::remote_signals::emit_and_relay;
+
 // Called the first time a zome call is made to the cell containing this zome
 #[hdk_extern]
 pub fn init() -> ExternResult<InitCallbackResult> {
+    // Let any agent deliver note signals to this cell, see `remote_signals`
+    let mut functions = BTreeSet::new();
+    functions.insert((zome_info()?.name, "recv_remote_signal".into()));
+    create_cap_grant(CapGrantEntry {
+        tag: "recv_remote_signal".into(),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed(functions),
+    })?;
     Ok(InitCallbackResult::Pass)
 }
 
@@ -55,7 +80,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
             if let Ok(Some(link_type)) =
                 LinkTypes::from_type(create_link.zome_index, create_link.link_type)
             {
-                emit_signal(Signal::LinkCreated { action, link_type })?;
+                emit_and_relay(Signal::LinkCreated { action, link_type })?;
             }
             Ok(())
         }
@@ -70,7 +95,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                     if let Ok(Some(link_type)) =
                         LinkTypes::from_type(create_link.zome_index, create_link.link_type)
                     {
-                        emit_signal(Signal::LinkDeleted {
+                        emit_and_relay(Signal::LinkDeleted {
                             action,
                             link_type,
                             create_link_action: record.signed_action.clone(),
@@ -85,7 +110,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
         }
         Action::Create(_create) => {
             if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
-                emit_signal(Signal::EntryCreated { action, app_entry })?;
+                emit_and_relay(Signal::EntryCreated { action, app_entry })?;
             }
             Ok(())
         }
@@ -94,7 +119,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                 if let Ok(Some(original_app_entry)) =
                     get_entry_for_action(&update.original_action_address)
                 {
-                    emit_signal(Signal::EntryUpdated {
+                    emit_and_relay(Signal::EntryUpdated {
                         action,
                         app_entry,
                         original_app_entry,
@@ -105,7 +130,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
         }
         Action::Delete(delete) => {
             if let Ok(Some(original_app_entry)) = get_entry_for_action(&delete.deletes_address) {
-                emit_signal(Signal::EntryDeleted {
+                emit_and_relay(Signal::EntryDeleted {
                     action,
                     original_app_entry,
                 })?;
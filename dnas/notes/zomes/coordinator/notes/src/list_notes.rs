@@ -1,8 +1,146 @@
 use hdk::prelude::*;
 use notes_integrity::*;
+use std::cmp::Reverse;
 
+use crate::heads::{notes_heads, NoteHeads};
+use crate::time_index::{
+    get_legacy_list_notes_links, get_note_links_page, get_time_indexed_links, note_created_at,
+    NotesCursor, TimeOrder,
+};
+
+// Returns every note, newest first, including those still only linked from the legacy anchor
+// Prefer `get_notes_in_range`, which pages through the time index instead of loading it all
 #[hdk_extern]
 pub fn get_list_notes() -> ExternResult<Vec<Link>> {
-    let path = Path::from("list_notes");
-    get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::ListNotes)?.build())
+    let mut links = get_time_indexed_links(
+        Timestamp::from_micros(0),
+        Timestamp::MAX,
+        usize::MAX,
+        TimeOrder::NewestFirst,
+    )?;
+    links.extend(get_legacy_list_notes_links()?);
+    links.sort_by_key(|link| Reverse(note_created_at(link)));
+    Ok(links)
+}
+
+pub const EXCERPT_LENGTH: usize = 200;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListNotesInput {
+    pub limit: usize,
+    pub cursor: Option<NotesCursor>,
+    #[serde(default)]
+    pub sort: TimeOrder,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteListItem {
+    pub original_note_hash: ActionHash,
+    pub record: Record,
+    pub title: String,
+    pub content: String,
+    pub format: NoteFormat,
+    // The start of the content as a single line of plain text, for list views
+    pub excerpt: String,
+    pub author: AgentPubKey,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    // Set when concurrent updates left more than one head, see `get_note_heads`
+    pub diverged: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListNotesPage {
+    pub notes: Vec<NoteListItem>,
+    pub next_cursor: Option<NotesCursor>,
+}
+
+// Pages through all notes, resolving each one to its latest revision in batched calls
+#[hdk_extern]
+pub fn list_notes_page(input: ListNotesInput) -> ExternResult<ListNotesPage> {
+    let page = get_note_links_page(
+        Timestamp::from_micros(0),
+        Timestamp::MAX,
+        input.limit,
+        input.cursor,
+        input.sort,
+    )?;
+    let original_note_hashes = page
+        .links
+        .into_iter()
+        .map(|link| {
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))
+        })
+        .collect::<ExternResult<Vec<ActionHash>>>()?;
+    Ok(ListNotesPage {
+        notes: get_note_list_items(original_note_hashes)?,
+        next_cursor: page.next_cursor,
+    })
+}
+
+// Resolves the latest revision of each note from its heads, see `get_latest_note`, in batched calls,
+// skipping notes that can no longer be found
+pub fn get_note_list_items(
+    original_note_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<NoteListItem>> {
+    let mut notes = vec![];
+    for note_heads in notes_heads(original_note_hashes)? {
+        let Some(NoteHeads {
+            original_record,
+            heads,
+        }) = note_heads
+        else {
+            continue;
+        };
+        let diverged = heads.len() > 1;
+        let Some(record) = heads.into_iter().next() else {
+            continue;
+        };
+        let Some(note) = record
+            .entry()
+            .to_app_option::<Note>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        notes.push(NoteListItem {
+            original_note_hash: original_record.action_address().clone(),
+            excerpt: note_excerpt(&note, EXCERPT_LENGTH),
+            title: note.title,
+            content: note.content,
+            format: note.format,
+            author: original_record.action().author().clone(),
+            created_at: original_record.action().timestamp(),
+            updated_at: record.action().timestamp(),
+            diverged,
+            record,
+        });
+    }
+    Ok(notes)
+}
+
+// Collapses the plain text of the note's content into a single line of at most `max_chars` chars
+pub fn note_excerpt(note: &Note, max_chars: usize) -> String {
+    let text = plain_text(note.format, &note.content);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut excerpt = words.join(" ");
+    if excerpt.chars().count() > max_chars {
+        excerpt = excerpt.chars().take(max_chars.saturating_sub(1)).collect();
+        excerpt = excerpt.trim_end().to_string();
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+// The excerpt of the latest revision of a note, stripped of the markup of its format
+#[hdk_extern]
+pub fn get_note_excerpt(original_note_hash: ActionHash) -> ExternResult<Option<String>> {
+    Ok(get_note_list_items(vec![original_note_hash])?
+        .into_iter()
+        .next()
+        .map(|item| item.excerpt))
 }
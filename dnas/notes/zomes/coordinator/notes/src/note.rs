@@ -1,45 +1,164 @@
 use hdk::prelude::*;
 use notes_integrity::*;
 
+use crate::heads::note_heads;
+use crate::references::{
+    all_revision_titles, resolve_note_references, set_note_references, set_note_title,
+};
+use crate::search::{add_note_terms, all_revision_terms, remove_note_terms, update_note_terms};
+use crate::tags::{add_note_tags, normalize_tags, remove_note_tags, update_note_tags};
+use crate::time_index::{
+    get_legacy_list_notes_links, index_note_by_time, remove_note_from_time_index,
+};
+use crate::trash::is_in_trash;
+
 #[hdk_extern]
-pub fn create_note(note: Note) -> ExternResult<Record> {
+pub fn create_note(mut note: Note) -> ExternResult<Record> {
+    note.tags = normalize_tags(note.tags);
     let note_hash = create_entry(&EntryTypes::Note(note.clone()))?; //return ActionHash
     let record = get(note_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         //tries to retrieve record for created note from dht
         WasmErrorInner::Guest("Could not find the newly created Note".to_string())
     ))?;
-    let path = Path::from("list_notes");
+    index_note(&note_hash, record.action().timestamp(), &note)?;
+    Ok(record)
+}
+
+// Links a note from the time index, its owner, its tags, its search terms and its title,
+// and to the notes it references
+pub fn index_note(
+    original_note_hash: &ActionHash,
+    created_at: Timestamp,
+    note: &Note,
+) -> ExternResult<()> {
+    index_note_by_time(original_note_hash.clone(), created_at)?;
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
     create_link(
-        //creating link
-        path.path_entry_hash()?, //from list_notes path
-        note_hash.clone(),       // to the newly created note(ActionHash)
-        LinkTypes::ListNotes,    //link type of ListNotes
-        (),                      //tag
+        my_agent_pub_key,
+        original_note_hash.clone(),
+        LinkTypes::AgentToNotes,
+        note_link_tag(original_note_hash),
     )?;
-    Ok(record)
+    add_note_tags(original_note_hash, &note.tags)?;
+    add_note_terms(original_note_hash, &note_terms(note))?;
+    set_note_title(original_note_hash, &[], Some(&note.title))?;
+    set_note_references(
+        original_note_hash,
+        &resolve_note_references(original_note_hash, note)?,
+    )?;
+    Ok(())
 }
 
-#[hdk_extern]
-pub fn get_latest_note(original_note_hash: ActionHash) -> ExternResult<Option<Record>> {
+// Index links are tagged with the hash of the note they point to, so that they can be found by tag prefix
+pub fn note_link_tag(original_note_hash: &ActionHash) -> LinkTag {
+    LinkTag::new(original_note_hash.get_raw_39())
+}
+
+// Looks up the links from `base` to the note by their tag
+pub fn get_links_to_note(
+    base: impl Into<AnyLinkableHash>,
+    link_type: LinkTypes,
+    original_note_hash: &ActionHash,
+) -> ExternResult<Vec<Link>> {
     let links = get_links(
-        // returns a vector of all links
-        GetLinksInputBuilder::try_new(original_note_hash.clone(), LinkTypes::NoteUpdates)?.build(),
+        GetLinksInputBuilder::try_new(base, link_type)?
+            .tag_prefix(note_link_tag(original_note_hash))
+            .build(),
     )?;
-    let latest_link = links
+    Ok(links
         .into_iter()
-        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp)); // picking the most recent links by timestamp from vector of links returned by get_links
-    let latest_note_hash = match latest_link {
-        Some(link) => {
-            link.target
-                .clone()
-                .into_action_hash()
-                .ok_or(wasm_error!(WasmErrorInner::Guest(
-                    "No action hash associated with link".to_string()
-                )))?
+        .filter(|link| link.target.clone().into_action_hash().as_ref() == Some(original_note_hash))
+        .collect())
+}
+
+// Removes every link that lists the note, leaving its entry and revisions in place
+// Returns the hashes of the links it deleted
+pub fn unindex_note(original_note_hash: &ActionHash) -> ExternResult<Vec<ActionHash>> {
+    let record = get(original_note_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the Note".to_string())
+    ))?;
+    let mut removed = remove_note_from_time_index(original_note_hash, record.action().timestamp())?;
+    // Notes created before the time index are only listed from the legacy anchor, whose links are untagged
+    // No new notes are added to it, so scanning it doesn't get slower as notes are created
+    if removed.is_empty() {
+        for link in get_legacy_list_notes_links()? {
+            if link.target.clone().into_action_hash().as_ref() == Some(original_note_hash) {
+                delete_link(link.create_link_hash.clone())?;
+                removed.push(link.create_link_hash);
+            }
         }
-        None => original_note_hash.clone(),
-    };
-    get(latest_note_hash, GetOptions::default())
+    }
+    // The tags of every head are indexed while the note has diverged
+    let mut tags: Vec<String> = vec![];
+    for head in note_heads(original_note_hash.clone())? {
+        for tag in get_note(head.action_address().clone())?.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    removed.extend(remove_note_tags(original_note_hash, &tags)?);
+    // Terms may have been indexed from any revision, not only the latest one
+    removed.extend(remove_note_terms(
+        original_note_hash,
+        &all_revision_terms(original_note_hash)?,
+    )?);
+    removed.extend(set_note_title(
+        original_note_hash,
+        &all_revision_titles(original_note_hash)?,
+        None,
+    )?);
+    removed.extend(set_note_references(original_note_hash, &[])?);
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    for link in get_links_to_note(
+        my_agent_pub_key,
+        LinkTypes::AgentToNotes,
+        original_note_hash,
+    )? {
+        delete_link(link.create_link_hash.clone())?;
+        removed.push(link.create_link_hash);
+    }
+    Ok(removed)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LatestNote {
+    pub record: Record,
+    // Set when concurrent updates left more than one head, see `get_note_heads`
+    pub diverged: bool,
+}
+
+#[hdk_extern]
+pub fn get_latest_note(original_note_hash: ActionHash) -> ExternResult<Option<LatestNote>> {
+    let mut heads = note_heads(original_note_hash)?;
+    let diverged = heads.len() > 1;
+    if heads.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(LatestNote {
+        record: heads.remove(0),
+        diverged,
+    }))
+}
+
+// Resolves the latest revision from update links, picking the newest one
+// Notes resolve theirs from their heads instead, see `get_latest_note`
+pub fn latest_note_hash(
+    original_note_hash: ActionHash,
+    note_updates_links: Vec<Link>,
+) -> ExternResult<ActionHash> {
+    let latest_link = note_updates_links
+        .into_iter()
+        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp)); // picking the most recent links by timestamp from vector of links returned by get_links
+    match latest_link {
+        Some(link) => link
+            .target
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            ))),
+        None => Ok(original_note_hash),
+    }
 }
 
 #[hdk_extern]
@@ -83,6 +202,20 @@ pub fn get_all_revisions_for_note(original_note_hash: ActionHash) -> ExternResul
     Ok(records)
 }
 
+// Fetches the note committed by the given action
+pub fn get_note(note_hash: ActionHash) -> ExternResult<Note> {
+    let record = get(note_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the Note".to_string())
+    ))?;
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UpdateNoteInput {
     pub original_note_hash: ActionHash,
@@ -92,12 +225,90 @@ pub struct UpdateNoteInput {
 
 #[hdk_extern]
 pub fn update_note(input: UpdateNoteInput) -> ExternResult<Record> {
+    let previous_note = get_note(input.previous_note_hash.clone())?;
+    commit_note_update(input, &[previous_note], vec![])
+}
+
+// Writes the update along with its NoteUpdates link, whose tag lists the other heads it merges if any,
+// and moves the note's tags, search terms, title and references from those of the revisions it updates to its own
+pub fn commit_note_update(
+    mut input: UpdateNoteInput,
+    previous_notes: &[Note],
+    merged_heads: Vec<ActionHash>,
+) -> ExternResult<Record> {
+    input.updated_note.tags = normalize_tags(input.updated_note.tags);
+    let original_record = get(input.original_note_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest("Could not find the Note".to_string())),
+    )?;
+    let owner = original_record.action().author().clone();
+    // Updating a trashed note would index it again, so it has to be restored first
+    if is_in_trash(owner.clone(), &input.original_note_hash)? {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Restore this note from the trash before updating it".to_string()
+        )));
+    }
+    let is_owner = owner == agent_info()?.agent_initial_pubkey;
+    // Editors name the state of the owner's chain they write against, see `validate_update_note`
+    input.updated_note.owner_chain_head = if is_owner {
+        None
+    } else {
+        Some(agent_chain_head(owner)?)
+    };
     let updated_note_hash = update_entry(input.previous_note_hash.clone(), &input.updated_note)?;
+    let tag: LinkTag = if merged_heads.is_empty() {
+        ().into()
+    } else {
+        LinkTag::new(
+            SerializedBytes::try_from(MergedHeads(merged_heads))
+                .map_err(|e| wasm_error!(e))?
+                .bytes()
+                .clone(),
+        )
+    };
     create_link(
         input.original_note_hash.clone(),
         updated_note_hash.clone(),
         LinkTypes::NoteUpdates,
-        (),
+        tag,
+    )?;
+    let mut previous_tags: Vec<String> = vec![];
+    let mut previous_terms: Vec<String> = vec![];
+    let mut previous_titles: Vec<String> = vec![];
+    for previous_note in previous_notes {
+        for tag in previous_note.tags.iter() {
+            if !previous_tags.contains(tag) {
+                previous_tags.push(tag.clone());
+            }
+        }
+        for term in note_terms(previous_note) {
+            if !previous_terms.contains(&term) {
+                previous_terms.push(term);
+            }
+        }
+        let title = normalize_title(&previous_note.title);
+        if !previous_titles.contains(&title) {
+            previous_titles.push(title);
+        }
+    }
+    update_note_tags(
+        &input.original_note_hash,
+        &previous_tags,
+        &input.updated_note.tags,
+    )?;
+    // Editors maintain the search, title and reference links along with the owner, see `may_index_note`
+    update_note_terms(
+        &input.original_note_hash,
+        &previous_terms,
+        &note_terms(&input.updated_note),
+    )?;
+    set_note_title(
+        &input.original_note_hash,
+        &previous_titles,
+        Some(&input.updated_note.title),
+    )?;
+    set_note_references(
+        &input.original_note_hash,
+        &resolve_note_references(&input.original_note_hash, &input.updated_note)?,
     )?;
     let record = get(updated_note_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest("Could not find the newly updated Note".to_string())
@@ -105,20 +316,54 @@ pub fn update_note(input: UpdateNoteInput) -> ExternResult<Record> {
     Ok(record)
 }
 
+// The newest action of the agent's chain that the DHT has validated
+fn agent_chain_head(agent: AgentPubKey) -> ExternResult<ActionHash> {
+    get_agent_activity(agent, ChainQueryFilter::new(), ActivityRequest::Full)?
+        .valid_activity
+        .into_iter()
+        .max_by_key(|(action_seq, _)| *action_seq)
+        .map(|(_, action_hash)| action_hash)
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Could not find the chain of the owner of the Note".to_string()
+        )))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeleteNoteReport {
+    pub original_note_hash: ActionHash,
+    // The Delete action of the original note
+    pub delete_action_hash: ActionHash,
+    // The revisions of the note that were deleted along with it
+    pub deleted_revisions: Vec<ActionHash>,
+    // The index and reference links that pointed to the note
+    pub removed_links: Vec<ActionHash>,
+}
+
+// Takes the note out of every index and deletes it along with all of its revisions
 #[hdk_extern]
-pub fn delete_note(original_note_hash: ActionHash) -> ExternResult<ActionHash> {
-    let path = Path::from("list_notes");
+pub fn delete_note(original_note_hash: ActionHash) -> ExternResult<DeleteNoteReport> {
+    let removed_links = unindex_note(&original_note_hash)?;
     let links = get_links(
-        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::ListNotes)?.build(),
+        GetLinksInputBuilder::try_new(original_note_hash.clone(), LinkTypes::NoteUpdates)?.build(),
     )?;
+    let mut deleted_revisions: Vec<ActionHash> = vec![];
     for link in links {
-        if let Some(hash) = link.target.into_action_hash() {
-            if hash == original_note_hash {
-                delete_link(link.create_link_hash)?;
-            }
+        let Some(revision_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if deleted_revisions.contains(&revision_hash) {
+            continue;
         }
+        delete_entry(revision_hash.clone())?;
+        deleted_revisions.push(revision_hash);
     }
-    delete_entry(original_note_hash)
+    let delete_action_hash = delete_entry(original_note_hash.clone())?;
+    Ok(DeleteNoteReport {
+        original_note_hash,
+        delete_action_hash,
+        deleted_revisions,
+        removed_links,
+    })
 }
 
 #[hdk_extern]
@@ -0,0 +1,212 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::note::latest_note_hash;
+
+// Top-level notebooks hang off their author's AgentPubKey
+fn children_base(parent: Option<ActionHash>) -> ExternResult<AnyLinkableHash> {
+    Ok(match parent {
+        Some(parent) => parent.into(),
+        None => agent_info()?.agent_initial_pubkey.into(),
+    })
+}
+
+#[hdk_extern]
+pub fn create_notebook(notebook: Notebook) -> ExternResult<Record> {
+    let notebook_hash = create_entry(&EntryTypes::Notebook(notebook.clone()))?;
+    create_link(
+        children_base(notebook.parent)?,
+        notebook_hash.clone(),
+        LinkTypes::NotebookToChildren,
+        (),
+    )?;
+    let record = get(notebook_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created Notebook".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_latest_notebook(original_notebook_hash: ActionHash) -> ExternResult<Option<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(original_notebook_hash.clone(), LinkTypes::NotebookUpdates)?
+            .build(),
+    )?;
+    get(
+        latest_note_hash(original_notebook_hash, links)?,
+        GetOptions::default(),
+    )
+}
+
+fn notebook_from_record(record: Record) -> ExternResult<Notebook> {
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))
+}
+
+fn get_notebook(notebook_hash: ActionHash) -> ExternResult<Notebook> {
+    let record = get_latest_notebook(notebook_hash)?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        "Could not find the Notebook".to_string()
+    )))?;
+    notebook_from_record(record)
+}
+
+// The revision an update builds on, which is also what the update changes from
+fn get_notebook_revision(notebook_hash: ActionHash) -> ExternResult<Notebook> {
+    let record = get(notebook_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the previous Notebook".to_string())
+    ))?;
+    notebook_from_record(record)
+}
+
+fn commit_notebook_update(
+    original_notebook_hash: ActionHash,
+    previous_notebook_hash: ActionHash,
+    notebook: Notebook,
+) -> ExternResult<Record> {
+    let updated_notebook_hash = update_entry(previous_notebook_hash, &notebook)?;
+    create_link(
+        original_notebook_hash,
+        updated_notebook_hash.clone(),
+        LinkTypes::NotebookUpdates,
+        (),
+    )?;
+    let record = get(updated_notebook_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly updated Notebook".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RenameNotebookInput {
+    pub original_notebook_hash: ActionHash,
+    pub previous_notebook_hash: ActionHash,
+    pub name: String,
+}
+
+#[hdk_extern]
+pub fn rename_notebook(input: RenameNotebookInput) -> ExternResult<Record> {
+    let mut notebook = get_notebook_revision(input.previous_notebook_hash.clone())?;
+    notebook.name = input.name;
+    commit_notebook_update(
+        input.original_notebook_hash,
+        input.previous_notebook_hash,
+        notebook,
+    )
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MoveNotebookInput {
+    pub original_notebook_hash: ActionHash,
+    pub previous_notebook_hash: ActionHash,
+    // None moves the notebook to the top level
+    pub parent: Option<ActionHash>,
+}
+
+// Nests the notebook in another one, refusing to move it inside itself or one of its descendants
+#[hdk_extern]
+pub fn move_notebook(input: MoveNotebookInput) -> ExternResult<Record> {
+    let mut notebook = get_notebook_revision(input.previous_notebook_hash.clone())?;
+    let mut ancestor = input.parent.clone();
+    while let Some(ancestor_hash) = ancestor {
+        if ancestor_hash == input.original_notebook_hash {
+            return Err(wasm_error!(WasmErrorInner::Guest(
+                "A notebook cannot be moved inside itself or one of its descendants".to_string()
+            )));
+        }
+        ancestor = get_notebook(ancestor_hash)?.parent;
+    }
+    let previous_parent = std::mem::replace(&mut notebook.parent, input.parent.clone());
+    let record = commit_notebook_update(
+        input.original_notebook_hash.clone(),
+        input.previous_notebook_hash,
+        notebook,
+    )?;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            children_base(previous_parent)?,
+            LinkTypes::NotebookToChildren,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if link.target.into_action_hash().as_ref() == Some(&input.original_notebook_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    create_link(
+        children_base(input.parent)?,
+        input.original_notebook_hash,
+        LinkTypes::NotebookToChildren,
+        (),
+    )?;
+    Ok(record)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MoveNoteInput {
+    pub original_note_hash: ActionHash,
+    // The notebook the note is currently in, if any
+    pub from_notebook: Option<ActionHash>,
+    // None takes the note out of any notebook
+    pub to_notebook: Option<ActionHash>,
+}
+
+#[hdk_extern]
+pub fn move_note(input: MoveNoteInput) -> ExternResult<()> {
+    if let Some(from_notebook) = input.from_notebook {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(from_notebook, LinkTypes::NotebookToNotes)?.build(),
+        )?;
+        for link in links {
+            if link.target.into_action_hash().as_ref() == Some(&input.original_note_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    if let Some(to_notebook) = input.to_notebook {
+        create_link(
+            to_notebook,
+            input.original_note_hash,
+            LinkTypes::NotebookToNotes,
+            (),
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotebookContents {
+    pub notebooks: Vec<Link>,
+    pub notes: Vec<Link>,
+}
+
+#[hdk_extern]
+pub fn get_notebook_contents(original_notebook_hash: ActionHash) -> ExternResult<NotebookContents> {
+    let notebooks = get_links(
+        GetLinksInputBuilder::try_new(
+            original_notebook_hash.clone(),
+            LinkTypes::NotebookToChildren,
+        )?
+        .build(),
+    )?;
+    let notes = get_links(
+        GetLinksInputBuilder::try_new(original_notebook_hash, LinkTypes::NotebookToNotes)?.build(),
+    )?;
+    Ok(NotebookContents { notebooks, notes })
+}
+
+#[hdk_extern]
+pub fn get_my_notebooks() -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(
+            agent_info()?.agent_initial_pubkey,
+            LinkTypes::NotebookToChildren,
+        )?
+        .build(),
+    )
+}
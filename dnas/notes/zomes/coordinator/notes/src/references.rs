@@ -0,0 +1,198 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::list_notes::get_note_list_items;
+use crate::note::{get_all_revisions_for_note, get_links_to_note, note_link_tag};
+
+// Resolves a `[[...]]` reference to the original hash of a note, either from its hash or from its title
+// Titles are looked up in the title index, preferring the agent's own notes and then the oldest one
+fn resolve_reference(reference: &str) -> ExternResult<Option<ActionHash>> {
+    if let Ok(hash) = ActionHash::try_from(reference) {
+        let Some(record) = get(hash.clone(), GetOptions::default())? else {
+            return Ok(None);
+        };
+        let note_type: EntryType = UnitEntryTypes::Note.try_into()?;
+        return Ok(match record.action() {
+            Action::Create(create) if create.entry_type == note_type => Some(hash),
+            _ => None,
+        });
+    }
+    let title = normalize_title(reference);
+    if title.is_empty() {
+        return Ok(None);
+    }
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            title_path(&title).path_entry_hash()?,
+            LinkTypes::TitleToNotes,
+        )?
+        .build(),
+    )?;
+    let mut original_note_hashes: Vec<ActionHash> = vec![];
+    for link in links {
+        if let Some(hash) = link.target.into_action_hash() {
+            if !original_note_hashes.contains(&hash) {
+                original_note_hashes.push(hash);
+            }
+        }
+    }
+    // The index may still list a note under a title it has since changed
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    Ok(get_note_list_items(original_note_hashes)?
+        .into_iter()
+        .filter(|note| normalize_title(&note.title) == title)
+        .min_by_key(|note| (note.author != my_agent_pub_key, note.created_at))
+        .map(|note| note.original_note_hash))
+}
+
+// The normalized titles of every revision of the note, which include all those the owner may have indexed
+pub fn all_revision_titles(original_note_hash: &ActionHash) -> ExternResult<Vec<String>> {
+    let mut titles: Vec<String> = vec![];
+    for record in get_all_revisions_for_note(original_note_hash.clone())? {
+        let Some(note) = record
+            .entry()
+            .to_app_option::<Note>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        let title = normalize_title(&note.title);
+        if !titles.contains(&title) {
+            titles.push(title);
+        }
+    }
+    Ok(titles)
+}
+
+// Links the note from its normalized title, unlinking it from the other titles in `previous_titles`
+// Pass no title to remove the note from the title index
+// Returns the hashes of the links it deleted
+pub fn set_note_title(
+    original_note_hash: &ActionHash,
+    previous_titles: &[String],
+    title: Option<&str>,
+) -> ExternResult<Vec<ActionHash>> {
+    let title = title.map(normalize_title).filter(|title| !title.is_empty());
+    let mut titles: Vec<String> = title.iter().cloned().collect();
+    for previous_title in previous_titles {
+        if !previous_title.is_empty() && !titles.contains(previous_title) {
+            titles.push(previous_title.clone());
+        }
+    }
+    let get_links_input = titles
+        .iter()
+        .map(|title| {
+            Ok(GetLinksInputBuilder::try_new(
+                title_path(title).path_entry_hash()?,
+                LinkTypes::TitleToNotes,
+            )?
+            .tag_prefix(title_link_tag(original_note_hash, title))
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let title_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    let mut removed: Vec<ActionHash> = vec![];
+    for (indexed_title, links) in titles.iter().zip(title_links) {
+        // The tag prefix of a title also matches the titles it is a prefix of
+        let tag = title_link_tag(original_note_hash, indexed_title);
+        let links: Vec<Link> = links.into_iter().filter(|link| link.tag == tag).collect();
+        if title.as_ref() != Some(indexed_title) {
+            for link in links {
+                delete_link(link.create_link_hash.clone())?;
+                removed.push(link.create_link_hash);
+            }
+        } else if links.is_empty() {
+            create_link(
+                title_path(indexed_title).path_entry_hash()?,
+                original_note_hash.clone(),
+                LinkTypes::TitleToNotes,
+                tag,
+            )?;
+        }
+    }
+    Ok(removed)
+}
+
+// The notes referenced by the content of the note, leaving out those that cannot be resolved
+pub fn resolve_note_references(
+    original_note_hash: &ActionHash,
+    note: &Note,
+) -> ExternResult<Vec<ActionHash>> {
+    let mut targets: Vec<ActionHash> = vec![];
+    for reference in note_references(note) {
+        if let Some(target) = resolve_reference(&reference)? {
+            if target != *original_note_hash && !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    Ok(targets)
+}
+
+// Links the note to the given notes and back, unlinking the notes it no longer references
+// Returns the hashes of the links it deleted
+pub fn set_note_references(
+    original_note_hash: &ActionHash,
+    targets: &[ActionHash],
+) -> ExternResult<Vec<ActionHash>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(original_note_hash.clone(), LinkTypes::NoteReferences)?
+            .build(),
+    )?;
+    let mut linked: Vec<ActionHash> = vec![];
+    // The targets whose backlinks were already cleaned up
+    let mut cleaned_up: Vec<ActionHash> = vec![];
+    let mut removed: Vec<ActionHash> = vec![];
+    for link in links {
+        let Some(target) = link.target.into_action_hash() else {
+            continue;
+        };
+        if targets.contains(&target) && !linked.contains(&target) {
+            linked.push(target);
+            continue;
+        }
+        delete_link(link.create_link_hash.clone())?;
+        removed.push(link.create_link_hash);
+        if cleaned_up.contains(&target) {
+            continue;
+        }
+        // The duplicates of a kept link, written by concurrent updates, keep one backlink for it
+        let kept_backlinks = usize::from(targets.contains(&target));
+        for backlink in
+            get_links_to_note(target.clone(), LinkTypes::NoteBacklinks, original_note_hash)?
+                .into_iter()
+                .skip(kept_backlinks)
+        {
+            delete_link(backlink.create_link_hash.clone())?;
+            removed.push(backlink.create_link_hash);
+        }
+        cleaned_up.push(target);
+    }
+    for target in targets.iter().filter(|target| !linked.contains(target)) {
+        create_link(
+            original_note_hash.clone(),
+            target.clone(),
+            LinkTypes::NoteReferences,
+            note_link_tag(target),
+        )?;
+        create_link(
+            target.clone(),
+            original_note_hash.clone(),
+            LinkTypes::NoteBacklinks,
+            note_link_tag(original_note_hash),
+        )?;
+    }
+    Ok(removed)
+}
+
+// The links to the notes referenced by the latest revision of the note, as of when it was last written
+#[hdk_extern]
+pub fn get_outgoing_links(original_note_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(original_note_hash, LinkTypes::NoteReferences)?.build())
+}
+
+// The links to the notes whose content references the note
+#[hdk_extern]
+pub fn get_backlinks(original_note_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(GetLinksInputBuilder::try_new(original_note_hash, LinkTypes::NoteBacklinks)?.build())
+}
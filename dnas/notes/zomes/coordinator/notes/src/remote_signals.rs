@@ -0,0 +1,126 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::subscriptions::get_subscribers;
+use crate::Signal;
+
+// Emits the signal to the local UI and forwards it to the other agents it concerns
+pub fn emit_and_relay(signal: Signal) -> ExternResult<()> {
+    emit_signal(&signal)?;
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    let mut agents = signal_recipients(&signal)?;
+    agents.retain(|agent| *agent != my_agent_pub_key);
+    if agents.is_empty() {
+        return Ok(());
+    }
+    send_remote_signal(&signal, agents)
+}
+
+// The owner and editors of a note, the recipients of an encrypted note or of a share,
+// and the subscribers of a note or its owner when it is updated or deleted
+fn signal_recipients(signal: &Signal) -> ExternResult<Vec<AgentPubKey>> {
+    let mut agents: Vec<AgentPubKey> = vec![];
+    let mut add_agent = |agent: &AgentPubKey| {
+        if !agents.contains(agent) {
+            agents.push(agent.clone());
+        }
+    };
+    match signal {
+        Signal::EntryCreated { app_entry, .. } => {
+            entry_agents(app_entry).iter().for_each(&mut add_agent);
+        }
+        Signal::EntryUpdated {
+            action,
+            app_entry,
+            original_app_entry,
+        } => {
+            if let (Action::Update(update), EntryTypes::Note(_)) = (action.action(), app_entry) {
+                let (original_note_hash, owner) =
+                    resolve_original_note(update.original_action_address.clone())?;
+                add_agent(&owner);
+                subscribers(original_note_hash, owner)?
+                    .iter()
+                    .for_each(&mut add_agent);
+            }
+            entry_agents(app_entry).iter().for_each(&mut add_agent);
+            entry_agents(original_app_entry)
+                .iter()
+                .for_each(&mut add_agent);
+        }
+        Signal::EntryDeleted {
+            action,
+            original_app_entry,
+        } => {
+            if let (Action::Delete(delete), EntryTypes::Note(_)) =
+                (action.action(), original_app_entry)
+            {
+                let (original_note_hash, owner) =
+                    resolve_original_note(delete.deletes_address.clone())?;
+                subscribers(original_note_hash, owner)?
+                    .iter()
+                    .for_each(&mut add_agent);
+            }
+            entry_agents(original_app_entry)
+                .iter()
+                .for_each(&mut add_agent);
+        }
+        Signal::LinkCreated {
+            action: create_link_action,
+            link_type,
+        }
+        | Signal::LinkDeleted {
+            create_link_action,
+            link_type,
+            ..
+        } => {
+            if let (LinkTypes::SharedWith, Action::CreateLink(create_link)) =
+                (link_type, create_link_action.action())
+            {
+                if let Some(recipient) = create_link.base_address.clone().into_agent_pub_key() {
+                    add_agent(&recipient);
+                }
+            }
+        }
+    }
+    Ok(agents)
+}
+
+fn subscribers(
+    original_note_hash: ActionHash,
+    owner: AgentPubKey,
+) -> ExternResult<Vec<AgentPubKey>> {
+    let mut agents = get_subscribers(original_note_hash.into())?;
+    agents.extend(get_subscribers(owner.into())?);
+    Ok(agents)
+}
+
+fn entry_agents(app_entry: &EntryTypes) -> Vec<AgentPubKey> {
+    match app_entry {
+        EntryTypes::Note(note) => note.editors.clone(),
+        EntryTypes::EncryptedNote(encrypted_note) => encrypted_note
+            .envelopes
+            .iter()
+            .map(|envelope| envelope.recipient.clone())
+            .collect(),
+        // Notebooks only concern their author
+        EntryTypes::Notebook(_) => vec![],
+        // Attachments are announced by the links of their notes
+        EntryTypes::AttachmentChunk(_) | EntryTypes::AttachmentManifest(_) => vec![],
+    }
+}
+
+// Re-emits signals sent by other agents to the local UI, dropping those about actions the sender did not author
+#[hdk_extern]
+pub fn recv_remote_signal(signal: Signal) -> ExternResult<()> {
+    let action = match &signal {
+        Signal::LinkCreated { action, .. }
+        | Signal::LinkDeleted { action, .. }
+        | Signal::EntryCreated { action, .. }
+        | Signal::EntryUpdated { action, .. }
+        | Signal::EntryDeleted { action, .. } => action,
+    };
+    if *action.action().author() != call_info()?.provenance {
+        return Ok(());
+    }
+    emit_signal(signal)
+}
@@ -0,0 +1,221 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+use std::cmp::Reverse;
+
+use crate::list_notes::{get_note_list_items, NoteListItem};
+use crate::note::get_all_revisions_for_note;
+
+// Chars of content kept on each side of the first match in a snippet
+const SNIPPET_RADIUS: usize = 60;
+// Occurrences in the title weigh more than those in the content
+const TITLE_WEIGHT: usize = 3;
+
+pub fn add_note_terms(original_note_hash: &ActionHash, terms: &[String]) -> ExternResult<()> {
+    for term in terms {
+        create_link(
+            term_path(term).path_entry_hash()?,
+            original_note_hash.clone(),
+            LinkTypes::TermToNotes,
+            term_link_tag(original_note_hash, term),
+        )?;
+    }
+    Ok(())
+}
+
+// Fetches the links from each term to the note with one batched get_links, looking them up by tag
+fn get_term_links(
+    original_note_hash: &ActionHash,
+    terms: &[String],
+) -> ExternResult<Vec<Vec<Link>>> {
+    let get_links_input = terms
+        .iter()
+        .map(|term| {
+            Ok(GetLinksInputBuilder::try_new(
+                term_path(term).path_entry_hash()?,
+                LinkTypes::TermToNotes,
+            )?
+            .tag_prefix(term_link_tag(original_note_hash, term))
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let term_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    // The tag prefix of a term also matches the terms it is a prefix of
+    Ok(terms
+        .iter()
+        .zip(term_links)
+        .map(|(term, links)| {
+            let tag = term_link_tag(original_note_hash, term);
+            links.into_iter().filter(|link| link.tag == tag).collect()
+        })
+        .collect())
+}
+
+// The terms of every revision of the note, which include all those the owner may have indexed
+pub fn all_revision_terms(original_note_hash: &ActionHash) -> ExternResult<Vec<String>> {
+    let mut terms: Vec<String> = vec![];
+    for record in get_all_revisions_for_note(original_note_hash.clone())? {
+        let Some(note) = record
+            .entry()
+            .to_app_option::<Note>()
+            .map_err(|e| wasm_error!(e))?
+        else {
+            continue;
+        };
+        for term in note_terms(&note) {
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+    }
+    Ok(terms)
+}
+
+// Returns the hashes of the links it deleted
+pub fn remove_note_terms(
+    original_note_hash: &ActionHash,
+    terms: &[String],
+) -> ExternResult<Vec<ActionHash>> {
+    let mut removed = vec![];
+    for link in get_term_links(original_note_hash, terms)?
+        .into_iter()
+        .flatten()
+    {
+        delete_link(link.create_link_hash.clone())?;
+        removed.push(link.create_link_hash);
+    }
+    Ok(removed)
+}
+
+// Removes the links for terms only in `old_terms` and adds those for terms of `new_terms` not indexed yet
+pub fn update_note_terms(
+    original_note_hash: &ActionHash,
+    old_terms: &[String],
+    new_terms: &[String],
+) -> ExternResult<()> {
+    let mut terms: Vec<String> = new_terms.to_vec();
+    terms.extend(
+        old_terms
+            .iter()
+            .filter(|term| !new_terms.contains(term))
+            .cloned(),
+    );
+    let term_links = get_term_links(original_note_hash, &terms)?;
+    let mut added: Vec<String> = vec![];
+    for (term, links) in terms.into_iter().zip(term_links) {
+        if !new_terms.contains(&term) {
+            for link in links {
+                delete_link(link.create_link_hash)?;
+            }
+        } else if links.is_empty() {
+            added.push(term);
+        }
+    }
+    add_note_terms(original_note_hash, &added)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchHit {
+    pub note: NoteListItem,
+    // How many of the query terms the note contains
+    pub matched_terms: usize,
+    pub score: usize,
+    pub snippet: String,
+}
+
+// Finds the notes containing any of the terms of the query, those matching the most terms first,
+// then those where they occur the most
+// Hits are checked against the latest revision of each note, which drops stale index links
+#[hdk_extern]
+pub fn search_notes(query: String) -> ExternResult<Vec<SearchHit>> {
+    let mut query_terms: Vec<String> = vec![];
+    for term in tokenize(&query) {
+        if !query_terms.contains(&term) {
+            query_terms.push(term);
+        }
+    }
+    let get_links_input = query_terms
+        .iter()
+        .map(|term| {
+            Ok(GetLinksInputBuilder::try_new(
+                term_path(term).path_entry_hash()?,
+                LinkTypes::TermToNotes,
+            )?
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let term_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    let mut original_note_hashes: Vec<ActionHash> = vec![];
+    for link in term_links.into_iter().flatten() {
+        if let Some(hash) = link.target.into_action_hash() {
+            if !original_note_hashes.contains(&hash) {
+                original_note_hashes.push(hash);
+            }
+        }
+    }
+    let mut hits: Vec<SearchHit> = get_note_list_items(original_note_hashes)?
+        .into_iter()
+        .filter_map(|note| search_hit(note, &query_terms))
+        .collect();
+    hits.sort_by_key(|hit| {
+        (
+            Reverse(hit.matched_terms),
+            Reverse(hit.score),
+            Reverse(hit.note.updated_at),
+        )
+    });
+    Ok(hits)
+}
+
+fn search_hit(note: NoteListItem, query_terms: &[String]) -> Option<SearchHit> {
+    let title_terms = tokenize(&note.title);
+    let text = plain_text(note.format, &note.content);
+    let content_tokens = tokenize_with_offsets(&text);
+    let mut matched_terms = 0;
+    let mut score = 0;
+    for query_term in query_terms {
+        let occurrences = title_terms
+            .iter()
+            .filter(|term| *term == query_term)
+            .count()
+            * TITLE_WEIGHT
+            + content_tokens
+                .iter()
+                .filter(|(_, _, term)| term == query_term)
+                .count();
+        if occurrences > 0 {
+            matched_terms += 1;
+            score += occurrences;
+        }
+    }
+    if matched_terms == 0 {
+        return None;
+    }
+    let first_match = content_tokens
+        .iter()
+        .find(|(_, _, term)| query_terms.contains(term))
+        .map(|(offset, length, _)| (*offset, *length));
+    let snippet = snippet(&text, first_match);
+    Some(SearchHit {
+        note,
+        matched_terms,
+        score,
+        snippet,
+    })
+}
+
+// The plain text around the first match, or its beginning when only the title matched
+fn snippet(content: &str, first_match: Option<(usize, usize)>) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let (offset, length) = first_match.unwrap_or((0, 0));
+    let start = offset.saturating_sub(SNIPPET_RADIUS);
+    let end = (offset + length + SNIPPET_RADIUS).min(chars.len());
+    let snippet: String = chars[start..end].iter().collect();
+    let mut snippet = snippet.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
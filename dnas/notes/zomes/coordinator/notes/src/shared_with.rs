@@ -0,0 +1,100 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::encrypted_note::{
+    commit_encrypted_note_revision, ensure_my_encryption_key, get_encrypted_note,
+    latest_encrypted_note_hash, open_content_key, seal_content_key,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareNoteInput {
+    pub original_encrypted_note_hash: ActionHash,
+    pub recipients: Vec<AgentPubKey>,
+}
+
+// Seals the current content key of the note to each new recipient and links the note from their AgentPubKey
+#[hdk_extern]
+pub fn share_note(input: ShareNoteInput) -> ExternResult<Record> {
+    let previous_hash = latest_encrypted_note_hash(input.original_encrypted_note_hash.clone())?;
+    let mut encrypted_note = get_encrypted_note(previous_hash.clone())?;
+    let key_ref = open_content_key(&encrypted_note)?;
+    let my_key = ensure_my_encryption_key()?;
+    let mut new_recipients: Vec<AgentPubKey> = vec![];
+    for recipient in input.recipients {
+        if encrypted_note
+            .envelopes
+            .iter()
+            .any(|envelope| envelope.recipient == recipient)
+            || new_recipients.contains(&recipient)
+        {
+            continue;
+        }
+        new_recipients.push(recipient);
+    }
+    for recipient in new_recipients.iter() {
+        encrypted_note.envelopes.push(seal_content_key(
+            key_ref.clone(),
+            my_key,
+            recipient.clone(),
+        )?);
+    }
+    let record = commit_encrypted_note_revision(
+        input.original_encrypted_note_hash.clone(),
+        previous_hash,
+        encrypted_note,
+    )?;
+    // The link points at the revision holding the recipient's envelope, which validation checks
+    for recipient in new_recipients {
+        create_link(
+            recipient,
+            input.original_encrypted_note_hash.clone(),
+            LinkTypes::SharedWith,
+            shared_with_link_tag(record.action_address()),
+        )?;
+    }
+    Ok(record)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevokeShareInput {
+    pub original_encrypted_note_hash: ActionHash,
+    pub recipient: AgentPubKey,
+}
+
+// Drops the recipient's envelope from the note and removes the share link
+// The content key is rotated on the next update, so the recipient cannot read any later revision
+#[hdk_extern]
+pub fn revoke_share(input: RevokeShareInput) -> ExternResult<Record> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    if input.recipient == my_agent_pub_key {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "You cannot revoke your own access to a note".to_string()
+        )));
+    }
+    let previous_hash = latest_encrypted_note_hash(input.original_encrypted_note_hash.clone())?;
+    let mut encrypted_note = get_encrypted_note(previous_hash.clone())?;
+    encrypted_note
+        .envelopes
+        .retain(|envelope| envelope.recipient != input.recipient);
+    let record = commit_encrypted_note_revision(
+        input.original_encrypted_note_hash.clone(),
+        previous_hash,
+        encrypted_note,
+    )?;
+    let links =
+        get_links(GetLinksInputBuilder::try_new(input.recipient, LinkTypes::SharedWith)?.build())?;
+    for link in links {
+        if link.target.into_action_hash().as_ref() == Some(&input.original_encrypted_note_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_notes_shared_with_me() -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(agent_info()?.agent_initial_pubkey, LinkTypes::SharedWith)?
+            .build(),
+    )
+}
@@ -0,0 +1,87 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+// Links the local agent both ways with the note or author they follow, unless they already do
+fn subscribe(subscribable: AnyLinkableHash) -> ExternResult<()> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    if get_subscribers(subscribable.clone())?.contains(&my_agent_pub_key) {
+        return Ok(());
+    }
+    create_link(
+        subscribable.clone(),
+        my_agent_pub_key.clone(),
+        LinkTypes::Subscribers,
+        (),
+    )?;
+    create_link(my_agent_pub_key, subscribable, LinkTypes::Subscriptions, ())?;
+    Ok(())
+}
+
+fn unsubscribe(subscribable: AnyLinkableHash) -> ExternResult<()> {
+    let my_agent_pub_key = agent_info()?.agent_initial_pubkey;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(subscribable.clone(), LinkTypes::Subscribers)?.build(),
+    )?;
+    for link in links {
+        if link.author == my_agent_pub_key {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    let links = get_links(
+        GetLinksInputBuilder::try_new(my_agent_pub_key, LinkTypes::Subscriptions)?.build(),
+    )?;
+    for link in links {
+        if link.target == subscribable {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+// The agents following a note or an author
+pub fn get_subscribers(subscribable: AnyLinkableHash) -> ExternResult<Vec<AgentPubKey>> {
+    let links =
+        get_links(GetLinksInputBuilder::try_new(subscribable, LinkTypes::Subscribers)?.build())?;
+    Ok(links
+        .into_iter()
+        .filter_map(|link| link.target.into_agent_pub_key())
+        .collect())
+}
+
+#[hdk_extern]
+pub fn subscribe_to_note(original_note_hash: ActionHash) -> ExternResult<()> {
+    subscribe(original_note_hash.into())
+}
+
+#[hdk_extern]
+pub fn unsubscribe_from_note(original_note_hash: ActionHash) -> ExternResult<()> {
+    unsubscribe(original_note_hash.into())
+}
+
+#[hdk_extern]
+pub fn subscribe_to_author(author: AgentPubKey) -> ExternResult<()> {
+    subscribe(author.into())
+}
+
+#[hdk_extern]
+pub fn unsubscribe_from_author(author: AgentPubKey) -> ExternResult<()> {
+    unsubscribe(author.into())
+}
+
+// Lets the profile zome notify the followers of an agent when they edit their profile
+#[hdk_extern]
+pub fn get_author_subscribers(author: AgentPubKey) -> ExternResult<Vec<AgentPubKey>> {
+    get_subscribers(author.into())
+}
+
+// The targets of the links are the original action hashes of followed notes and the AgentPubKeys of followed authors
+#[hdk_extern]
+pub fn get_my_subscriptions() -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(
+            agent_info()?.agent_initial_pubkey,
+            LinkTypes::Subscriptions,
+        )?
+        .build(),
+    )
+}
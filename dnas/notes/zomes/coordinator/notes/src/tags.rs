@@ -0,0 +1,139 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+// Trims, lowercases and dedups the tags given by the client, dropping empty ones
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = vec![];
+    for tag in tags {
+        let tag = normalize_tag(&tag);
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+pub fn add_note_tags(original_note_hash: &ActionHash, tags: &[String]) -> ExternResult<()> {
+    for tag in tags {
+        let path = tag_path(tag).typed(LinkTypes::AllTags)?;
+        path.ensure()?;
+        create_link(
+            path.path_entry_hash()?,
+            original_note_hash.clone(),
+            LinkTypes::TagToNotes,
+            note_tag_link_tag(original_note_hash, tag),
+        )?;
+    }
+    Ok(())
+}
+
+// Returns the hashes of the links it deleted
+pub fn remove_note_tags(
+    original_note_hash: &ActionHash,
+    tags: &[String],
+) -> ExternResult<Vec<ActionHash>> {
+    // Looked up by tag prefix, so that the cost doesn't grow with the number of notes with each tag
+    let get_links_input = tags
+        .iter()
+        .map(|tag| {
+            Ok(GetLinksInputBuilder::try_new(
+                tag_path(tag).path_entry_hash()?,
+                LinkTypes::TagToNotes,
+            )?
+            .tag_prefix(note_tag_link_tag(original_note_hash, tag))
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let tag_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    let mut removed = vec![];
+    for (tag, links) in tags.iter().zip(tag_links) {
+        // The prefix of a tag also matches the tags it is a prefix of
+        let link_tag = note_tag_link_tag(original_note_hash, tag);
+        for link in links.into_iter().filter(|link| link.tag == link_tag) {
+            delete_link(link.create_link_hash.clone())?;
+            removed.push(link.create_link_hash);
+        }
+    }
+    Ok(removed)
+}
+
+// Adds the links for tags only in `new_tags` and removes those for tags only in `old_tags`
+pub fn update_note_tags(
+    original_note_hash: &ActionHash,
+    old_tags: &[String],
+    new_tags: &[String],
+) -> ExternResult<()> {
+    let removed: Vec<String> = old_tags
+        .iter()
+        .filter(|tag| !new_tags.contains(tag))
+        .cloned()
+        .collect();
+    let added: Vec<String> = new_tags
+        .iter()
+        .filter(|tag| !old_tags.contains(tag))
+        .cloned()
+        .collect();
+    remove_note_tags(original_note_hash, &removed)?;
+    add_note_tags(original_note_hash, &added)
+}
+
+#[hdk_extern]
+pub fn get_notes_by_tag(tag: String) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(
+            tag_path(&normalize_tag(&tag)).path_entry_hash()?,
+            LinkTypes::TagToNotes,
+        )?
+        .build(),
+    )
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+// Lists every tag still attached to at least one note, most used first
+#[hdk_extern]
+pub fn list_all_tags() -> ExternResult<Vec<TagCount>> {
+    let root = Path::from(vec![Component::from(ALL_TAGS_ROOT)]);
+    // Tags that cannot be read are skipped rather than failing the whole listing
+    let mut tags: Vec<String> = vec![];
+    for link in get_links(
+        GetLinksInputBuilder::try_new(root.path_entry_hash()?, LinkTypes::AllTags)?.build(),
+    )? {
+        if let Some(tag) = path_link_component(&link.tag) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    let get_links_input = tags
+        .iter()
+        .map(|tag| {
+            Ok(GetLinksInputBuilder::try_new(
+                tag_path(tag).path_entry_hash()?,
+                LinkTypes::TagToNotes,
+            )?
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let tag_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    let mut tag_counts: Vec<TagCount> = tags
+        .into_iter()
+        .zip(tag_links)
+        .map(|(tag, links)| TagCount {
+            tag,
+            count: links.len(),
+        })
+        .filter(|tag_count| tag_count.count > 0)
+        .collect();
+    tag_counts.sort_by(|tag_a, tag_b| {
+        tag_b
+            .count
+            .cmp(&tag_a.count)
+            .then_with(|| tag_a.tag.cmp(&tag_b.tag))
+    });
+    Ok(tag_counts)
+}
@@ -0,0 +1,272 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+use std::cmp::Reverse;
+
+use crate::note::get_links_to_note;
+
+pub fn index_note_by_time(note_hash: ActionHash, created_at: Timestamp) -> ExternResult<()> {
+    let components = time_bucket_components(created_at);
+    ensure_time_bucket(&components)?;
+    create_link(
+        time_bucket_prefix_path(&components).path_entry_hash()?,
+        note_hash.clone(),
+        LinkTypes::NotesByTime,
+        notes_by_time_link_tag(&note_hash, created_at),
+    )?;
+    Ok(())
+}
+
+// Links the bucket from its parent, and so on up to the root, stopping at the first bucket already linked
+fn ensure_time_bucket(components: &[String]) -> ExternResult<()> {
+    for depth in (1..=components.len()).rev() {
+        let tag = time_bucket_link_tag(&components[..depth]);
+        let parent_hash = time_bucket_prefix_path(&components[..depth - 1]).path_entry_hash()?;
+        let exists = get_links(
+            GetLinksInputBuilder::try_new(parent_hash.clone(), LinkTypes::NotesByTimePath)?
+                .tag_prefix(tag.clone())
+                .build(),
+        )?
+        .into_iter()
+        .any(|link| link.tag == tag);
+        if exists {
+            break;
+        }
+        create_link(
+            parent_hash,
+            time_bucket_prefix_path(&components[..depth]).path_entry_hash()?,
+            LinkTypes::NotesByTimePath,
+            tag,
+        )?;
+    }
+    Ok(())
+}
+
+// Returns the hashes of the links it deleted
+pub fn remove_note_from_time_index(
+    note_hash: &ActionHash,
+    created_at: Timestamp,
+) -> ExternResult<Vec<ActionHash>> {
+    let path = time_bucket_path(created_at);
+    let links = get_links_to_note(path.path_entry_hash()?, LinkTypes::NotesByTime, note_hash)?;
+    let mut removed = vec![];
+    for link in links {
+        delete_link(link.create_link_hash.clone())?;
+        removed.push(link.create_link_hash);
+    }
+    Ok(removed)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+// The position of the last note of a page: its creation time, with its hash breaking ties
+// between notes created at the same time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NotesCursor {
+    pub created_at: Timestamp,
+    pub note_hash: ActionHash,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetNotesInRangeInput {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub limit: usize,
+    pub cursor: Option<NotesCursor>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotesInRangePage {
+    pub links: Vec<Link>,
+    pub next_cursor: Option<NotesCursor>,
+}
+
+// The links of the time index a query reads: those to notes created in `[start, end)` that come
+// after the cursor in the given order
+struct TimeWindow<'a> {
+    start: Timestamp,
+    end: Timestamp,
+    cursor: Option<&'a NotesCursor>,
+    order: TimeOrder,
+}
+
+impl TimeWindow<'_> {
+    fn contains(&self, link: &Link) -> bool {
+        let created_at = note_created_at(link);
+        if created_at < self.start || created_at >= self.end {
+            return false;
+        }
+        let Some(cursor) = self.cursor else {
+            return true;
+        };
+        let position = link_position(link);
+        let cursor_position = (cursor.created_at, Some(cursor.note_hash.clone()));
+        match self.order {
+            TimeOrder::NewestFirst => position < cursor_position,
+            TimeOrder::OldestFirst => position > cursor_position,
+        }
+    }
+}
+
+// Returns the notes created in `[start, end)`, newest first
+// Pass the returned `next_cursor` back in as `cursor` to get the following page
+#[hdk_extern]
+pub fn get_notes_in_range(input: GetNotesInRangeInput) -> ExternResult<NotesInRangePage> {
+    get_note_links_page(
+        input.start,
+        input.end,
+        input.limit,
+        input.cursor,
+        TimeOrder::NewestFirst,
+    )
+}
+
+// The cursor is the position of the last link of the previous page, which is excluded from this one
+// Notes created at the cursor's time but not yet returned are still included
+pub fn get_note_links_page(
+    start: Timestamp,
+    end: Timestamp,
+    limit: usize,
+    cursor: Option<NotesCursor>,
+    order: TimeOrder,
+) -> ExternResult<NotesInRangePage> {
+    let (start, end) = match (order, &cursor) {
+        (TimeOrder::NewestFirst, Some(cursor)) => (
+            start,
+            end.min(Timestamp::from_micros(cursor.created_at.as_micros() + 1)),
+        ),
+        (TimeOrder::OldestFirst, Some(cursor)) => (start.max(cursor.created_at), end),
+        (_, None) => (start, end),
+    };
+    if limit == 0 || end <= start {
+        return Ok(NotesInRangePage {
+            links: vec![],
+            next_cursor: None,
+        });
+    }
+    let window = TimeWindow {
+        start,
+        end,
+        cursor: cursor.as_ref(),
+        order,
+    };
+    let mut links = time_indexed_links(&window, limit + 1)?;
+    links.extend(
+        get_legacy_list_notes_links()?
+            .into_iter()
+            .filter(|link| window.contains(link)),
+    );
+    sort_links(&mut links, order);
+    let next_cursor = if links.len() > limit {
+        links.truncate(limit);
+        links.last().and_then(|link| match link_position(link) {
+            (created_at, Some(note_hash)) => Some(NotesCursor {
+                created_at,
+                note_hash,
+            }),
+            _ => None,
+        })
+    } else {
+        None
+    };
+    Ok(NotesInRangePage { links, next_cursor })
+}
+
+// Walks the time buckets in the given order, stopping once at least `limit` links were found
+pub fn get_time_indexed_links(
+    start: Timestamp,
+    end: Timestamp,
+    limit: usize,
+    order: TimeOrder,
+) -> ExternResult<Vec<Link>> {
+    time_indexed_links(
+        &TimeWindow {
+            start,
+            end,
+            cursor: None,
+            order,
+        },
+        limit,
+    )
+}
+
+fn time_indexed_links(window: &TimeWindow, limit: usize) -> ExternResult<Vec<Link>> {
+    let lower = time_bucket_components(window.start);
+    let upper = time_bucket_components(window.end);
+    let mut links = vec![];
+    collect_links(&[], &lower, &upper, window, limit, &mut links)?;
+    Ok(links)
+}
+
+fn collect_links(
+    components: &[String],
+    lower: &[String],
+    upper: &[String],
+    window: &TimeWindow,
+    limit: usize,
+    links: &mut Vec<Link>,
+) -> ExternResult<()> {
+    let path_hash = time_bucket_prefix_path(components).path_entry_hash()?;
+    let depth = components.len();
+    if depth == lower.len() {
+        let mut bucket_links =
+            get_links(GetLinksInputBuilder::try_new(path_hash, LinkTypes::NotesByTime)?.build())?;
+        bucket_links.retain(|link| window.contains(link));
+        sort_links(&mut bucket_links, window.order);
+        links.extend(bucket_links);
+        return Ok(());
+    }
+    // Children whose tag cannot be read are skipped rather than failing the whole query
+    let mut children: Vec<Vec<String>> =
+        get_links(GetLinksInputBuilder::try_new(path_hash, LinkTypes::NotesByTimePath)?.build())?
+            .into_iter()
+            .filter_map(|link| parse_time_bucket_link_tag(&link.tag))
+            .filter(|child| child.len() == depth + 1 && child.starts_with(components))
+            .collect();
+    children.sort();
+    children.dedup();
+    if window.order == TimeOrder::NewestFirst {
+        children.reverse();
+    }
+    for child in children {
+        if child.as_slice() > &upper[..=depth] || child.as_slice() < &lower[..=depth] {
+            continue;
+        }
+        collect_links(&child, lower, upper, window, limit, links)?;
+        if links.len() >= limit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+// Links are ordered by the creation time of their note, then by its hash for notes created at the same time
+fn sort_links(links: &mut [Link], order: TimeOrder) {
+    match order {
+        TimeOrder::NewestFirst => links.sort_by_key(|link| Reverse(link_position(link))),
+        TimeOrder::OldestFirst => links.sort_by_key(link_position),
+    }
+}
+
+fn link_position(link: &Link) -> (Timestamp, Option<ActionHash>) {
+    (
+        note_created_at(link),
+        link.target.clone().into_action_hash(),
+    )
+}
+
+// When the note a time index link points to was created
+// Legacy ListNotes links are untagged, and were written along with the note
+pub fn note_created_at(link: &Link) -> Timestamp {
+    notes_by_time_link_created_at(&link.tag).unwrap_or(link.timestamp)
+}
+
+// Notes created before the time index existed are only linked from the global anchor
+pub fn get_legacy_list_notes_links() -> ExternResult<Vec<Link>> {
+    let path = Path::from("list_notes");
+    get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::ListNotes)?.build())
+}
@@ -0,0 +1,109 @@
+use hdk::prelude::*;
+use notes_integrity::*;
+
+use crate::note::{
+    delete_note, get_latest_note, get_note, index_note, unindex_note, DeleteNoteReport,
+};
+
+fn get_trash_links(original_note_hash: &ActionHash) -> ExternResult<Vec<Link>> {
+    Ok(list_trash(())?
+        .into_iter()
+        .filter(|link| link.target.clone().into_action_hash().as_ref() == Some(original_note_hash))
+        .collect())
+}
+
+// Takes the note out of every index and links it from the local agent's trash
+// Its entry and revisions are kept, so that it can be restored
+#[hdk_extern]
+pub fn trash_note(original_note_hash: ActionHash) -> ExternResult<ActionHash> {
+    if !get_trash_links(&original_note_hash)?.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "This note is already in the trash".to_string()
+        )));
+    }
+    unindex_note(&original_note_hash)?;
+    create_link(
+        agent_info()?.agent_initial_pubkey,
+        original_note_hash,
+        LinkTypes::AgentToTrash,
+        (),
+    )
+}
+
+// Whether the owner of the note has moved it to their trash
+pub fn is_in_trash(owner: AgentPubKey, original_note_hash: &ActionHash) -> ExternResult<bool> {
+    Ok(
+        get_links(GetLinksInputBuilder::try_new(owner, LinkTypes::AgentToTrash)?.build())?
+            .into_iter()
+            .any(|link| link.target.into_action_hash().as_ref() == Some(original_note_hash)),
+    )
+}
+
+#[hdk_extern]
+pub fn list_trash() -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(agent_info()?.agent_initial_pubkey, LinkTypes::AgentToTrash)?
+            .build(),
+    )
+}
+
+// Recreates the index links of a trashed note from its latest revision
+#[hdk_extern]
+pub fn restore_note(original_note_hash: ActionHash) -> ExternResult<()> {
+    let links = get_trash_links(&original_note_hash)?;
+    if links.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "This note is not in the trash".to_string()
+        )));
+    }
+    let record = get(original_note_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the Note to restore".to_string())
+    ))?;
+    let latest = get_latest_note(original_note_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the Note to restore".to_string())
+    ))?;
+    let note = get_note(latest.record.action_address().clone())?;
+    index_note(&original_note_hash, record.action().timestamp(), &note)?;
+    for link in links {
+        delete_link(link.create_link_hash)?;
+    }
+    Ok(())
+}
+
+// Deletes a trashed note for good
+#[hdk_extern]
+pub fn purge_note(original_note_hash: ActionHash) -> ExternResult<DeleteNoteReport> {
+    let links = get_trash_links(&original_note_hash)?;
+    if links.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Only notes in the trash can be purged".to_string()
+        )));
+    }
+    for link in links {
+        delete_link(link.create_link_hash)?;
+    }
+    delete_note(original_note_hash)
+}
+
+// Purges the notes that have been in the trash for longer than the `trash_retention_seconds` DNA property
+// Meant to be called by the UI on startup, returns the original hashes of the purged notes
+#[hdk_extern]
+pub fn purge_expired_trash() -> ExternResult<Vec<ActionHash>> {
+    let retention_micros = notes_properties()?.trash_retention_seconds * 1_000_000;
+    let now = sys_time()?;
+    let mut purged: Vec<ActionHash> = vec![];
+    for link in list_trash(())? {
+        if now.as_micros() - link.timestamp.as_micros() < retention_micros {
+            continue;
+        }
+        let Some(original_note_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if purged.contains(&original_note_hash) {
+            continue;
+        }
+        purge_note(original_note_hash.clone())?;
+        purged.push(original_note_hash);
+    }
+    Ok(purged)
+}
@@ -0,0 +1,35 @@
+use hdk::prelude::*;
+use profile_integrity::*;
+
+// Returns the entry hash to reference from `Profile::avatar`
+#[hdk_extern]
+pub fn create_avatar(avatar: Avatar) -> ExternResult<EntryHash> {
+    if let Some(error) = validate_avatar(&avatar) {
+        return Err(wasm_error!(WasmErrorInner::Guest(error)));
+    }
+    let avatar_hash = hash_entry(&avatar)?;
+    create_entry(&EntryTypes::Avatar(avatar))?;
+    Ok(avatar_hash)
+}
+
+#[hdk_extern]
+pub fn get_avatar(avatar_hash: EntryHash) -> ExternResult<Option<Avatar>> {
+    let Some(record) = get(avatar_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
+
+// Checks the avatar a profile references before committing it, the same way validation will
+pub fn check_profile_avatar(avatar_hash: &Option<EntryHash>) -> ExternResult<()> {
+    let Some(avatar_hash) = avatar_hash else {
+        return Ok(());
+    };
+    let avatar = get_avatar(avatar_hash.clone())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        "Could not find the avatar of the profile".to_string()
+    )))?;
+    if let Some(error) = validate_avatar(&avatar) {
+        return Err(wasm_error!(WasmErrorInner::Guest(error)));
+    }
+    Ok(())
+}
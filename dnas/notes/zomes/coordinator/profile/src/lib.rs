@@ -1,16 +1,55 @@
+pub mod avatar;
+pub mod nickname_index;
+pub mod profile;
 use hdk::prelude::*;
 use profile_integrity::*;
 
 // Called the first time a zome call is made to the cell containing this zome
 #[hdk_extern]
 pub fn init() -> ExternResult<InitCallbackResult> {
+    // Let any agent deliver profile update signals to this cell
+    let mut functions = BTreeSet::new();
+    functions.insert((zome_info()?.name, "recv_remote_signal".into()));
+    create_cap_grant(CapGrantEntry {
+        tag: "recv_remote_signal".into(),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed(functions),
+    })?;
     Ok(InitCallbackResult::Pass)
 }
 
 // Don't modify this enum if you want the scaffolding tool to generate appropriate signals for your entries and links
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
-pub enum Signal {}
+pub enum Signal {
+    LinkCreated {
+        action: SignedActionHashed,
+        link_type: LinkTypes,
+    },
+    LinkDeleted {
+        action: SignedActionHashed,
+        create_link_action: SignedActionHashed,
+        link_type: LinkTypes,
+    },
+    EntryCreated {
+        action: SignedActionHashed,
+        app_entry: EntryTypes,
+    },
+    EntryUpdated {
+        action: SignedActionHashed,
+        app_entry: EntryTypes,
+        original_app_entry: EntryTypes,
+    },
+    EntryDeleted {
+        action: SignedActionHashed,
+        original_app_entry: EntryTypes,
+    },
+    // Sent to the agents following someone when they edit their profile, so that UIs refresh cached nicknames
+    ProfileUpdated {
+        agent: AgentPubKey,
+        profile: Profile,
+    },
+}
 
 // Whenever an action is committed, we emit a signal to the UI elements to reactively update them
 #[hdk_extern(infallible)]
@@ -25,5 +64,138 @@ pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
 
 // Don't modify this function if you want the scaffolding tool to generate appropriate signals for your entries and links
 fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
+    match action.hashed.content.clone() {
+        Action::CreateLink(create_link) => {
+            if let Ok(Some(link_type)) =
+                LinkTypes::from_type(create_link.zome_index, create_link.link_type)
+            {
+                emit_signal(Signal::LinkCreated { action, link_type })?;
+            }
+            Ok(())
+        }
+        Action::DeleteLink(delete_link) => {
+            let record = get(delete_link.link_add_address.clone(), GetOptions::default())?.ok_or(
+                wasm_error!(WasmErrorInner::Guest(
+                    "Failed to fetch CreateLink action".to_string()
+                )),
+            )?;
+            match record.action() {
+                Action::CreateLink(create_link) => {
+                    if let Ok(Some(link_type)) =
+                        LinkTypes::from_type(create_link.zome_index, create_link.link_type)
+                    {
+                        emit_signal(Signal::LinkDeleted {
+                            action,
+                            link_type,
+                            create_link_action: record.signed_action.clone(),
+                        })?;
+                    }
+                    Ok(())
+                }
+                _ => Err(wasm_error!(WasmErrorInner::Guest(
+                    "Create Link should exist".to_string()
+                ))),
+            }
+        }
+        Action::Create(_create) => {
+            if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
+                emit_signal(Signal::EntryCreated { action, app_entry })?;
+            }
+            Ok(())
+        }
+        Action::Update(update) => {
+            if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
+                let profile = match &app_entry {
+                    EntryTypes::Profile(profile) => Some(profile.clone()),
+                    _ => None,
+                };
+                if let Ok(Some(original_app_entry)) =
+                    get_entry_for_action(&update.original_action_address)
+                {
+                    emit_signal(Signal::EntryUpdated {
+                        action,
+                        app_entry,
+                        original_app_entry,
+                    })?;
+                }
+                // Other agents are notified after the local UI, and failing to reach them doesn't fail the signal
+                if let Some(profile) = profile {
+                    if let Err(err) = notify_profile_updated(update.author, profile) {
+                        error!(
+                            "Error notifying other agents of a profile update: {:?}",
+                            err
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        Action::Delete(delete) => {
+            if let Ok(Some(original_app_entry)) = get_entry_for_action(&delete.deletes_address) {
+                emit_signal(Signal::EntryDeleted {
+                    action,
+                    original_app_entry,
+                })?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
+    let record = match get_details(action_hash.clone(), GetOptions::default())? {
+        Some(Details::Record(record_details)) => record_details.record,
+        _ => return Ok(None),
+    };
+    let entry = match record.entry().as_option() {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+    let (zome_index, entry_index) = match record.action().entry_type() {
+        Some(EntryType::App(AppEntryDef {
+            zome_index,
+            entry_index,
+            ..
+        })) => (zome_index, entry_index),
+        _ => return Ok(None),
+    };
+    EntryTypes::deserialize_from_type(*zome_index, *entry_index, entry)
+}
+
+// Profile updates only go to the agents following the author in the notes zome, see `subscribe_to_author`
+fn notify_profile_updated(agent: AgentPubKey, profile: Profile) -> ExternResult<()> {
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from("notes"),
+        FunctionName::from("get_author_subscribers"),
+        None,
+        agent.clone(),
+    )?;
+    let ZomeCallResponse::Ok(result) = response else {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Could not get the subscribers of the author: {response:?}"
+        ))));
+    };
+    let agents: Vec<AgentPubKey> = result
+        .decode::<Vec<AgentPubKey>>()
+        .map_err(|e| wasm_error!(e))?
+        .into_iter()
+        .filter(|other| *other != agent)
+        .collect();
+    if agents.is_empty() {
+        return Ok(());
+    }
+    send_remote_signal(Signal::ProfileUpdated { agent, profile }, agents)
+}
+
+// Relays profile updates from other agents to the UI, dropping any signal not sent by the agent it is about
+#[hdk_extern]
+pub fn recv_remote_signal(signal: Signal) -> ExternResult<()> {
+    if let Signal::ProfileUpdated { agent, .. } = &signal {
+        if *agent == call_info()?.provenance {
+            emit_signal(signal)?;
+        }
+    }
     Ok(())
 }
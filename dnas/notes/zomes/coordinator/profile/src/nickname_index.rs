@@ -0,0 +1,100 @@
+use hdk::prelude::*;
+use profile_integrity::*;
+
+pub fn index_nickname(agent: AgentPubKey, nickname: &str) -> ExternResult<()> {
+    let nickname = nickname.trim().to_lowercase();
+    let path =
+        nickname_prefix_path(&nickname_prefix(&nickname)).typed(LinkTypes::NicknamePrefixes)?;
+    path.ensure()?;
+    create_link(
+        path.path_entry_hash()?,
+        agent,
+        LinkTypes::PrefixToAgents,
+        LinkTag::new(nickname.as_bytes()),
+    )?;
+    Ok(())
+}
+
+pub fn remove_nickname(agent: &AgentPubKey, nickname: &str) -> ExternResult<()> {
+    let path = nickname_prefix_path(&nickname_prefix(nickname));
+    let links = get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::PrefixToAgents)?.build(),
+    )?;
+    for link in links {
+        if link.target.into_agent_pub_key().as_ref() == Some(agent) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+// Returns the PrefixToAgents links whose nickname, held in the link tag, starts with the query
+#[hdk_extern]
+pub fn search_profiles(query: String) -> ExternResult<Vec<Link>> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(vec![]);
+    }
+    // Queries shorter than a prefix span all the buckets starting with them
+    let prefixes = if query.chars().count() >= NICKNAME_PREFIX_LENGTH {
+        vec![nickname_prefix(&query)]
+    } else {
+        all_nickname_prefixes()?
+            .into_iter()
+            .filter(|prefix| prefix.starts_with(&query))
+            .collect()
+    };
+    let links = get_prefix_links(prefixes)?;
+    Ok(links
+        .into_iter()
+        .filter(|link| {
+            String::from_utf8(link.tag.clone().into_inner())
+                .is_ok_and(|nickname| nickname.starts_with(&query))
+        })
+        .collect())
+}
+
+#[hdk_extern]
+pub fn get_all_agents_with_profile() -> ExternResult<Vec<AgentPubKey>> {
+    let mut agents: Vec<AgentPubKey> = vec![];
+    for link in get_prefix_links(all_nickname_prefixes()?)? {
+        if let Some(agent) = link.target.into_agent_pub_key() {
+            if !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+    }
+    Ok(agents)
+}
+
+fn all_nickname_prefixes() -> ExternResult<Vec<String>> {
+    let root = Path::from(vec![Component::from(ALL_NICKNAMES_ROOT)]);
+    // Prefixes that cannot be read are skipped rather than failing every lookup
+    let mut prefixes: Vec<String> = vec![];
+    for link in get_links(
+        GetLinksInputBuilder::try_new(root.path_entry_hash()?, LinkTypes::NicknamePrefixes)?
+            .build(),
+    )? {
+        if let Some(prefix) = nickname_path_link_component(&link.tag) {
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
+    }
+    Ok(prefixes)
+}
+
+fn get_prefix_links(prefixes: Vec<String>) -> ExternResult<Vec<Link>> {
+    let get_links_input = prefixes
+        .iter()
+        .map(|prefix| {
+            Ok(GetLinksInputBuilder::try_new(
+                nickname_prefix_path(prefix).path_entry_hash()?,
+                LinkTypes::PrefixToAgents,
+            )?
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+    Ok(links.into_iter().flatten().collect())
+}
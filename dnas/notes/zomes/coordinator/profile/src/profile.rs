@@ -1,12 +1,27 @@
 use hdk::prelude::*;
 use profile_integrity::*;
 
+use crate::avatar::check_profile_avatar;
+use crate::nickname_index::{index_nickname, remove_nickname};
+
 #[hdk_extern]
 pub fn create_profile(profile: Profile) -> ExternResult<Record> {
+    let agent = agent_info()?.agent_initial_pubkey;
+    if get_agent_profile_link(agent.clone())?.is_some() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "You already have a profile".to_string()
+        )));
+    }
+    check_profile_avatar(&profile.avatar)?;
     let profile_hash = create_entry(&EntryTypes::Profile(profile.clone()))?;
     // Link the agent to their profile
-    let agent = agent_info()?.agent_latest_pubkey;
-    create_link(agent.clone(), profile_hash.clone(), LinkTypes::AgentToProfile)?;
+    create_link(
+        agent.clone(),
+        profile_hash.clone(),
+        LinkTypes::AgentToProfile,
+        (),
+    )?;
+    index_nickname(agent, &profile.nickname)?;
 
     let record = get(profile_hash, GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest("Could not find the newly created Profile".to_string())
@@ -27,25 +42,121 @@ pub fn get_profile(profile_hash: ActionHash) -> ExternResult<Option<Record>> {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateProfileInput {
+    pub original_profile_hash: ActionHash,
+    pub previous_profile_hash: ActionHash,
+    pub updated_profile: Profile,
+}
+
 #[hdk_extern]
-pub fn update_profile(payload: (ActionHash, Profile)) -> ExternResult<Record> {
-    let (original_action_hash, updated_profile) = payload;
-    let updated_action_hash = update_entry(original_action_hash, &EntryTypes::Profile(updated_profile.clone()))?;
-    let record = get(updated_action_hash, GetOptions::default())?
-        .ok_or(wasm_error!(WasmErrorInner::Guest("Failed to fetch updated profile record".into())))?;
+pub fn update_profile(input: UpdateProfileInput) -> ExternResult<Record> {
+    let previous_record =
+        get(input.previous_profile_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Could not find the previous Profile".into())
+        ))?;
+    let previous_profile: Profile = previous_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    check_profile_avatar(&input.updated_profile.avatar)?;
+    let updated_action_hash = update_entry(
+        input.previous_profile_hash,
+        &EntryTypes::Profile(input.updated_profile.clone()),
+    )?;
+    create_link(
+        input.original_profile_hash,
+        updated_action_hash.clone(),
+        LinkTypes::ProfileUpdates,
+        (),
+    )?;
+    if previous_profile.nickname != input.updated_profile.nickname {
+        let agent = agent_info()?.agent_initial_pubkey;
+        remove_nickname(&agent, &previous_profile.nickname)?;
+        index_nickname(agent, &input.updated_profile.nickname)?;
+    }
+    let record = get(updated_action_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Failed to fetch updated profile record".into())
+    ))?;
     Ok(record)
 }
 
+#[hdk_extern]
+pub fn get_latest_profile(original_profile_hash: ActionHash) -> ExternResult<Option<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(original_profile_hash.clone(), LinkTypes::ProfileUpdates)?
+            .build(),
+    )?;
+    let latest_profile_hash = match links.into_iter().max_by_key(|link| link.timestamp) {
+        Some(link) => link
+            .target
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?,
+        None => original_profile_hash,
+    };
+    get(latest_profile_hash, GetOptions::default())
+}
+
+// The original profile followed by its updates, oldest first
+#[hdk_extern]
+pub fn get_profile_revisions(original_profile_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    let Some(original_record) = get_profile(original_profile_hash.clone())? else {
+        return Ok(vec![]);
+    };
+    let mut links = get_links(
+        GetLinksInputBuilder::try_new(original_profile_hash, LinkTypes::ProfileUpdates)?.build(),
+    )?;
+    links.sort_by_key(|link| link.timestamp);
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .map(|link| {
+            Ok(GetInput::new(
+                link.target
+                    .into_action_hash()
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(
+                        "No action hash associated with link".to_string()
+                    )))?
+                    .into(),
+                GetOptions::default(),
+            ))
+        })
+        .collect::<ExternResult<Vec<GetInput>>>()?;
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    let mut records: Vec<Record> = records.into_iter().flatten().collect();
+    records.insert(0, original_record);
+    Ok(records)
+}
+
+// Returns the newest revision of the local agent's profile
 #[hdk_extern]
 pub fn get_my_profile() -> ExternResult<Option<Record>> {
-    let agent = agent_info()?.agent_latest_pubkey;
+    get_agent_profile(agent_info()?.agent_initial_pubkey)
+}
 
-    let links = get_links(agent, LinkTypes::AgentToProfile, None)?;
-    let Some(link) = links.first() else {
+// Returns the newest revision of the given agent's profile
+#[hdk_extern]
+pub fn get_agent_profile(agent: AgentPubKey) -> ExternResult<Option<Record>> {
+    let Some(link) = get_agent_profile_link(agent)? else {
         return Ok(None);
     };
 
-    let profile_hash = ActionHash::from(link.target.clone());
-    let record = get(profile_hash, GetOptions::default())?;
-    Ok(record)
+    let profile_hash = link
+        .target
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    get_latest_profile(profile_hash)
+}
+
+// Validation only lets an agent create one profile, so the oldest link is the one that counts
+fn get_agent_profile_link(agent: AgentPubKey) -> ExternResult<Option<Link>> {
+    let links =
+        get_links(GetLinksInputBuilder::try_new(agent, LinkTypes::AgentToProfile)?.build())?;
+    Ok(links.into_iter().min_by_key(|link| link.timestamp))
 }
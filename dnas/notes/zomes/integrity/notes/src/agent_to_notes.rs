@@ -0,0 +1,59 @@
+use hdi::prelude::*;
+
+pub fn validate_create_link_agent_to_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = base_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "No AgentPubKey associated with the base of an AgentToNotes link".to_string(),
+        ));
+    };
+    if agent != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can link notes off their own AgentPubKey".to_string(),
+        ));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    if !crate::is_note_link_tag(&tag, &action_hash) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of an AgentToNotes link must be the hash of the note it points to".to_string(),
+        ));
+    }
+    let record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != agent {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AgentToNotes link can only point to a note authored by that agent".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can remove links off their own AgentPubKey".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,243 @@
+use hdi::prelude::*;
+
+// Well under the 4MB entry limit, so that a chunk and its action always fit in one entry
+pub const MAX_CHUNK_BYTES: usize = 1024 * 1024;
+pub const MAX_ATTACHMENT_NAME_LENGTH: usize = 255;
+
+// The MIME types that can be attached, along with the magic bytes their content must start with
+// RIFF files also need "WEBP" at offset 8 to be webp images, which `validate_mime_type` checks
+const ALLOWED_MIME_TYPES: [(&str, &[u8]); 5] = [
+    ("image/png", b"\x89PNG\r\n\x1a\n"),
+    ("image/jpeg", b"\xff\xd8\xff"),
+    ("image/gif", b"GIF8"),
+    ("image/webp", b"RIFF"),
+    ("application/pdf", b"%PDF-"),
+];
+
+// A slice of the bytes of an attached file
+// Chunks are content-addressed, so identical chunks of different files are stored once
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct AttachmentChunk {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+// Describes an attached file, whose bytes are the concatenation of its chunks
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct AttachmentManifest {
+    pub name: String,
+    pub mime_type: String,
+    pub size: u64,
+    #[serde(with = "serde_bytes")]
+    pub sha256: Vec<u8>,
+    pub chunks: Vec<EntryHash>,
+}
+
+fn validate_mime_type(mime_type: &str, first_chunk: &[u8]) -> Option<String> {
+    let Some((_, magic)) = ALLOWED_MIME_TYPES
+        .iter()
+        .find(|(allowed, _)| *allowed == mime_type)
+    else {
+        return Some(format!("Files of type {mime_type} cannot be attached"));
+    };
+    let is_webp = mime_type != "image/webp" || first_chunk.get(8..12) == Some(b"WEBP".as_slice());
+    if !first_chunk.starts_with(magic) || !is_webp {
+        return Some(format!(
+            "The content of the attachment is not of type {mime_type}"
+        ));
+    }
+    None
+}
+
+pub fn validate_create_attachment_chunk(
+    _action: EntryCreationAction,
+    attachment_chunk: AttachmentChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    if attachment_chunk.bytes.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Attachment chunks cannot be empty".to_string(),
+        ));
+    }
+    if attachment_chunk.bytes.len() > MAX_CHUNK_BYTES {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Attachment chunks cannot be larger than {MAX_CHUNK_BYTES} bytes"
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_attachment_chunk(
+    _action: Update,
+    _attachment_chunk: AttachmentChunk,
+    _original_action: EntryCreationAction,
+    _original_attachment_chunk: AttachmentChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "Attachment chunks cannot be updated".to_string(),
+    ))
+}
+
+pub fn validate_delete_attachment_chunk(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_attachment_chunk: AttachmentChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an attachment chunk can delete it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Checks the declared name, size and MIME type of the file against its chunks, and their hash
+pub fn validate_create_attachment_manifest(
+    _action: EntryCreationAction,
+    attachment_manifest: AttachmentManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    let properties = crate::notes_properties()?;
+    let name = &attachment_manifest.name;
+    if name.trim().is_empty() || name.chars().count() > MAX_ATTACHMENT_NAME_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Attachment names must have between 1 and {MAX_ATTACHMENT_NAME_LENGTH} characters"
+        )));
+    }
+    if name.contains(['/', '\\']) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Attachment names cannot contain path separators".to_string(),
+        ));
+    }
+    if attachment_manifest.size > properties.max_attachment_bytes {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Attachments cannot be larger than {} bytes",
+            properties.max_attachment_bytes
+        )));
+    }
+    let expected_chunks = attachment_manifest.size.div_ceil(MAX_CHUNK_BYTES as u64);
+    if attachment_manifest.chunks.is_empty()
+        || attachment_manifest.chunks.len() as u64 != expected_chunks
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "An attachment of {} bytes must be split in {expected_chunks} chunks",
+            attachment_manifest.size
+        )));
+    }
+    let mut bytes: Vec<u8> = Vec::with_capacity(attachment_manifest.size as usize);
+    for (index, chunk_hash) in attachment_manifest.chunks.iter().enumerate() {
+        let chunk = AttachmentChunk::try_from(must_get_entry(chunk_hash.clone())?.content)?;
+        // Every chunk but the last is full, so that a file is always chunked the same way
+        if index + 1 < attachment_manifest.chunks.len() && chunk.bytes.len() != MAX_CHUNK_BYTES {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Every chunk of an attachment but the last must hold {MAX_CHUNK_BYTES} bytes"
+            )));
+        }
+        bytes.extend(chunk.bytes);
+    }
+    if bytes.len() as u64 != attachment_manifest.size {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The size of an attachment must be the total size of its chunks".to_string(),
+        ));
+    }
+    if let Some(error) = validate_mime_type(&attachment_manifest.mime_type, &bytes) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    if hash_sha256(bytes)? != attachment_manifest.sha256 {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The sha256 of an attachment does not match its chunks".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_attachment_manifest(
+    _action: Update,
+    _attachment_manifest: AttachmentManifest,
+    _original_action: EntryCreationAction,
+    _original_attachment_manifest: AttachmentManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "Attachment manifests cannot be updated".to_string(),
+    ))
+}
+
+pub fn validate_delete_attachment_manifest(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_attachment_manifest: AttachmentManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an attachment can delete it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Links the original action of a note to the manifests of the files its owner attached to it
+pub fn validate_create_link_note_to_attachments(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let note_record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = note_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let Action::Create(create) = note_record.action() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Attachments must hang off the original action of a note".to_string(),
+        ));
+    };
+    if create.author != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can attach files to it".to_string(),
+        ));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _attachment_manifest: crate::AttachmentManifest = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an attachment can attach it to a note".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_note_to_attachments(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can remove its attachments".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
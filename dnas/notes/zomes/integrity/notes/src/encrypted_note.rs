@@ -0,0 +1,248 @@
+use hdi::prelude::*;
+
+pub const NONCE_BYTES: usize = 24;
+pub const MAC_BYTES: usize = 16;
+pub const SECRET_KEY_BYTES: usize = 32;
+
+// The note's content key, boxed from the sender's x25519 key to one recipient
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeyEnvelope {
+    pub recipient: AgentPubKey,
+    pub sender_key: X25519PubKey,
+    #[serde(with = "serde_bytes")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub sealed_key: Vec<u8>,
+}
+
+// A note whose content is secretboxed with a random content key, only readable by the envelope recipients
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct EncryptedNote {
+    #[serde(with = "serde_bytes")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub ciphertext: Vec<u8>,
+    pub envelopes: Vec<KeyEnvelope>,
+}
+
+// Only the structure is validated, as nobody but the recipients can see the plaintext
+pub fn validate_create_encrypted_note(
+    action: EntryCreationAction,
+    encrypted_note: EncryptedNote,
+) -> ExternResult<ValidateCallbackResult> {
+    let properties = crate::notes_properties()?;
+    if encrypted_note.nonce.len() != NONCE_BYTES {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Encrypted note nonce must be {NONCE_BYTES} bytes"
+        )));
+    }
+    if encrypted_note.ciphertext.len() <= MAC_BYTES {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Encrypted note ciphertext is too short to hold any content".to_string(),
+        ));
+    }
+    if encrypted_note.ciphertext.len() > properties.max_ciphertext_bytes {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Encrypted note ciphertext cannot be larger than {} bytes",
+            properties.max_ciphertext_bytes
+        )));
+    }
+    for (index, envelope) in encrypted_note.envelopes.iter().enumerate() {
+        if envelope.nonce.len() != NONCE_BYTES {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Key envelope nonce must be {NONCE_BYTES} bytes"
+            )));
+        }
+        if envelope.sealed_key.len() != SECRET_KEY_BYTES + MAC_BYTES {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Key envelope sealed key must be {} bytes",
+                SECRET_KEY_BYTES + MAC_BYTES
+            )));
+        }
+        if encrypted_note.envelopes[..index]
+            .iter()
+            .any(|other| other.recipient == envelope.recipient)
+        {
+            return Ok(ValidateCallbackResult::Invalid(
+                "An encrypted note can only hold one key envelope per recipient".to_string(),
+            ));
+        }
+    }
+    if !encrypted_note
+        .envelopes
+        .iter()
+        .any(|envelope| envelope.recipient == *action.author())
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An encrypted note must hold a key envelope for its author".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_encrypted_note(
+    action: Update,
+    _encrypted_note: EncryptedNote,
+    original_action: EntryCreationAction,
+    _original_encrypted_note: EncryptedNote,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an encrypted note can update it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_encrypted_note(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_encrypted_note: EncryptedNote,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an encrypted note can delete it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_encrypted_note_updates(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let original_record = must_get_valid_record(action_hash)?;
+    let _encrypted_note: crate::EncryptedNote = original_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let updated_record = must_get_valid_record(action_hash)?;
+    let _encrypted_note: crate::EncryptedNote = updated_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if action.author != *original_record.action().author()
+        || updated_record.action().author() != original_record.action().author()
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an encrypted note can link updates to it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_encrypted_note_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "EncryptedNoteUpdates links cannot be deleted".to_string(),
+    ))
+}
+
+pub fn validate_create_link_agent_to_encrypted_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.into_agent_pub_key() != Some(action.author.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can link encrypted notes off their own AgentPubKey"
+                .to_string(),
+        ));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _encrypted_note: crate::EncryptedNote = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AgentToEncryptedNotes link can only point to an encrypted note authored by that agent"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_encrypted_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can remove links off their own AgentPubKey".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// The link tag holds the agent's x25519 public key, which others box content keys to
+pub fn validate_create_link_agent_to_encryption_key(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let author = AnyLinkableHash::from(action.author.clone());
+    if base_address != author || target_address != author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Agents can only publish their own encryption key".to_string(),
+        ));
+    }
+    if X25519PubKey::try_from(tag.into_inner()).is_err() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of an AgentToEncryptionKey link must be an x25519 public key".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_encryption_key(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "AgentToEncryptionKey links cannot be deleted, as envelopes sealed to them must stay readable"
+            .to_string(),
+    ))
+}
@@ -0,0 +1,191 @@
+use hdi::prelude::*;
+
+// How the content of a note is meant to be rendered
+// Rich text is a JSON document of nested nodes, with the text in their "text" fields and their children in "content"
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum NoteFormat {
+    #[default]
+    Plain,
+    Markdown,
+    RichText,
+}
+
+// URL schemes that would run code when a rendered link is followed
+const SCRIPT_SCHEMES: [&str; 3] = ["javascript:", "vbscript:", "data:text/html"];
+
+// The script scheme of the URL, if it has one
+// Browsers ignore whitespace and control characters in a URL scheme, so "java\tscript:" still runs
+fn script_scheme(url: &str) -> Option<&'static str> {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    SCRIPT_SCHEMES
+        .into_iter()
+        .find(|scheme| url.starts_with(scheme))
+}
+
+fn script_url_error(scheme: &str) -> String {
+    format!("Note content cannot contain {scheme} URLs")
+}
+
+// Looks for raw HTML tags: a '<' directly followed by a letter or '/', with a '>' somewhere after it
+// Markdown autolinks such as `<https://holochain.org>` are allowed unless they point to a script URL
+fn find_html(text: &str) -> Option<String> {
+    for (index, _) in text.match_indices('<') {
+        let rest = &text[index + 1..];
+        let starts_tag = rest
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/');
+        if !starts_tag {
+            continue;
+        }
+        let Some((inner, _)) = rest.split_once('>') else {
+            continue;
+        };
+        let autolink = inner.contains(':') && !inner.contains(char::is_whitespace);
+        if !autolink {
+            return Some("Note content cannot contain raw HTML".to_string());
+        }
+        if let Some(scheme) = script_scheme(inner) {
+            return Some(script_url_error(scheme));
+        }
+    }
+    None
+}
+
+// Looks for script URLs where markdown turns them into links: inline link destinations `[text](url)`
+// and reference definitions `[label]: url`, leaving the same words in prose alone
+fn find_markdown_script_url(content: &str) -> Option<String> {
+    for (index, _) in content.match_indices("](") {
+        let destination = &content[index + 2..];
+        let destination = destination.trim_start().trim_start_matches('<');
+        if let Some(scheme) = script_scheme(destination) {
+            return Some(script_url_error(scheme));
+        }
+    }
+    for line in content.lines() {
+        let line = line.trim_start();
+        if let Some((label, destination)) = line.split_once("]:") {
+            if !label.starts_with('[') {
+                continue;
+            }
+            if let Some(scheme) = script_scheme(destination.trim_start().trim_start_matches('<')) {
+                return Some(script_url_error(scheme));
+            }
+        }
+    }
+    None
+}
+
+fn rich_text_strings(value: &serde_json::Value, strings: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(string) => strings.push(string.clone()),
+        serde_json::Value::Array(values) => values
+            .iter()
+            .for_each(|value| rich_text_strings(value, strings)),
+        serde_json::Value::Object(fields) => fields
+            .values()
+            .for_each(|value| rich_text_strings(value, strings)),
+        _ => {}
+    }
+}
+
+pub fn validate_note_format(format: NoteFormat, content: &str) -> Option<String> {
+    match format {
+        NoteFormat::Plain => None,
+        NoteFormat::Markdown => find_html(content).or_else(|| find_markdown_script_url(content)),
+        NoteFormat::RichText => {
+            let Ok(document) = serde_json::from_str::<serde_json::Value>(content) else {
+                return Some("Rich text content must be a JSON document".to_string());
+            };
+            if !document.is_object() {
+                return Some("Rich text content must be a JSON object".to_string());
+            }
+            let mut strings = vec![];
+            rich_text_strings(&document, &mut strings);
+            // Rich text strings are either text, which may hold tags, or attributes such as link hrefs
+            strings.iter().find_map(|string| {
+                script_scheme(string)
+                    .map(script_url_error)
+                    .or_else(|| find_html(string))
+            })
+        }
+    }
+}
+
+fn strip_markdown(content: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            continue;
+        }
+        let line = line.trim_start_matches(['#', '>']).trim_start();
+        let line = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| line.strip_prefix(marker))
+            .unwrap_or(line);
+        let line = match line.split_once(". ") {
+            Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => line,
+        };
+        lines.push(strip_markdown_inline(line));
+    }
+    lines.join("\n")
+}
+
+// Keeps the text of links and the alt text of images, dropping their URLs and the emphasis markers
+fn strip_markdown_inline(line: &str) -> String {
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        let (before, after) = rest.split_at(start);
+        text.push_str(before.strip_suffix('!').unwrap_or(before));
+        match after[1..].split_once("](") {
+            Some((label, tail)) if !label.contains('[') && tail.contains(')') => {
+                text.push_str(label);
+                rest = &tail[tail.find(')').unwrap_or(0) + 1..];
+            }
+            _ => {
+                text.push('[');
+                rest = &after[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text.replace(['*', '`', '~'], "")
+}
+
+fn rich_text_plain_text(value: &serde_json::Value, text: &mut String) {
+    let Some(node) = value.as_object() else {
+        return;
+    };
+    if let Some(serde_json::Value::String(string)) = node.get("text") {
+        text.push_str(string);
+    }
+    if let Some(serde_json::Value::Array(children)) = node.get("content") {
+        children
+            .iter()
+            .for_each(|child| rich_text_plain_text(child, text));
+        // Nodes with children are blocks, whose text is kept apart from the next block's
+        text.push('\n');
+    }
+}
+
+// The text of a note's content, without any of the markup of its format
+pub fn plain_text(format: NoteFormat, content: &str) -> String {
+    match format {
+        NoteFormat::Plain => content.to_string(),
+        NoteFormat::Markdown => strip_markdown(content),
+        NoteFormat::RichText => {
+            let mut text = String::new();
+            if let Ok(document) = serde_json::from_str::<serde_json::Value>(content) {
+                rich_text_plain_text(&document, &mut text);
+            }
+            text
+        }
+    }
+}
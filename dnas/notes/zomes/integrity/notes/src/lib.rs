@@ -1,7 +1,33 @@
+pub mod agent_to_notes;
+pub mod attachment;
+pub mod encrypted_note;
+pub mod format;
 pub mod note;
+pub mod notebook;
+pub mod properties;
+pub mod references;
+pub mod search;
+pub mod shared_with;
+pub mod subscriptions;
+pub mod tags;
+pub mod time_index;
+pub mod trash;
 use hdi::prelude::*;
 
+pub use agent_to_notes::*;
+pub use attachment::*;
+pub use encrypted_note::*;
+pub use format::*;
 pub use note::*;
+pub use notebook::*;
+pub use properties::*;
+pub use references::*;
+pub use search::*;
+pub use shared_with::*;
+pub use subscriptions::*;
+pub use tags::*;
+pub use time_index::*;
+pub use trash::*;
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -9,6 +35,10 @@ pub use note::*;
 #[unit_enum(UnitEntryTypes)]
 pub enum EntryTypes {
     Note(Note),
+    EncryptedNote(EncryptedNote),
+    Notebook(Notebook),
+    AttachmentChunk(AttachmentChunk),
+    AttachmentManifest(AttachmentManifest),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -16,6 +46,26 @@ pub enum EntryTypes {
 pub enum LinkTypes {
     NoteUpdates,
     ListNotes,
+    AgentToNotes,
+    NotesByTimePath,
+    NotesByTime,
+    AllTags,
+    TagToNotes,
+    EncryptedNoteUpdates,
+    AgentToEncryptedNotes,
+    AgentToEncryptionKey,
+    SharedWith,
+    Subscribers,
+    Subscriptions,
+    AgentToTrash,
+    TermToNotes,
+    NotebookUpdates,
+    NotebookToChildren,
+    NotebookToNotes,
+    NoteToAttachments,
+    NoteReferences,
+    NoteBacklinks,
+    TitleToNotes,
 }
 
 // Validation you perform during the genesis process. Nobody else on the network performs it, only you.
@@ -60,6 +110,23 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Note(note) => {
                     validate_create_note(EntryCreationAction::Create(action), note)
                 }
+                EntryTypes::EncryptedNote(encrypted_note) => validate_create_encrypted_note(
+                    EntryCreationAction::Create(action),
+                    encrypted_note,
+                ),
+                EntryTypes::Notebook(notebook) => {
+                    validate_create_notebook(EntryCreationAction::Create(action), notebook)
+                }
+                EntryTypes::AttachmentChunk(attachment_chunk) => validate_create_attachment_chunk(
+                    EntryCreationAction::Create(action),
+                    attachment_chunk,
+                ),
+                EntryTypes::AttachmentManifest(attachment_manifest) => {
+                    validate_create_attachment_manifest(
+                        EntryCreationAction::Create(action),
+                        attachment_manifest,
+                    )
+                }
             },
             OpEntry::UpdateEntry {
                 app_entry, action, ..
@@ -67,6 +134,23 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Note(note) => {
                     validate_create_note(EntryCreationAction::Update(action), note)
                 }
+                EntryTypes::EncryptedNote(encrypted_note) => validate_create_encrypted_note(
+                    EntryCreationAction::Update(action),
+                    encrypted_note,
+                ),
+                EntryTypes::Notebook(notebook) => {
+                    validate_create_notebook(EntryCreationAction::Update(action), notebook)
+                }
+                EntryTypes::AttachmentChunk(attachment_chunk) => validate_create_attachment_chunk(
+                    EntryCreationAction::Update(action),
+                    attachment_chunk,
+                ),
+                EntryTypes::AttachmentManifest(attachment_manifest) => {
+                    validate_create_attachment_manifest(
+                        EntryCreationAction::Update(action),
+                        attachment_manifest,
+                    )
+                }
             },
             _ => Ok(ValidateCallbackResult::Valid),
         },
@@ -97,6 +181,81 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         };
                         validate_update_note(action, note, original_create_action, original_note)
                     }
+                    EntryTypes::EncryptedNote(encrypted_note) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_encrypted_note =
+                            match EncryptedNote::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get EncryptedNote from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_encrypted_note(
+                            action,
+                            encrypted_note,
+                            original_create_action,
+                            original_encrypted_note,
+                        )
+                    }
+                    EntryTypes::Notebook(notebook) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_notebook = match Notebook::try_from(original_app_entry) {
+                            Ok(entry) => entry,
+                            Err(e) => {
+                                return Ok(ValidateCallbackResult::Invalid(format!(
+                                    "Expected to get Notebook from Record: {e:?}"
+                                )));
+                            }
+                        };
+                        validate_update_notebook(
+                            action,
+                            notebook,
+                            original_create_action,
+                            original_notebook,
+                        )
+                    }
+                    EntryTypes::AttachmentChunk(attachment_chunk) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_attachment_chunk =
+                            match AttachmentChunk::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get AttachmentChunk from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_attachment_chunk(
+                            action,
+                            attachment_chunk,
+                            original_create_action,
+                            original_attachment_chunk,
+                        )
+                    }
+                    EntryTypes::AttachmentManifest(attachment_manifest) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_attachment_manifest =
+                            match AttachmentManifest::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get AttachmentManifest from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_attachment_manifest(
+                            action,
+                            attachment_manifest,
+                            original_create_action,
+                            original_attachment_manifest,
+                        )
+                    }
                 }
             }
             _ => Ok(ValidateCallbackResult::Valid),
@@ -146,6 +305,32 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     original_action,
                     original_note,
                 ),
+                EntryTypes::EncryptedNote(original_encrypted_note) => {
+                    validate_delete_encrypted_note(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_encrypted_note,
+                    )
+                }
+                EntryTypes::Notebook(original_notebook) => validate_delete_notebook(
+                    delete_entry.clone().action,
+                    original_action,
+                    original_notebook,
+                ),
+                EntryTypes::AttachmentChunk(original_attachment_chunk) => {
+                    validate_delete_attachment_chunk(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_attachment_chunk,
+                    )
+                }
+                EntryTypes::AttachmentManifest(original_attachment_manifest) => {
+                    validate_delete_attachment_manifest(
+                        delete_entry.clone().action,
+                        original_action,
+                        original_attachment_manifest,
+                    )
+                }
             }
         }
         FlatOp::RegisterCreateLink {
@@ -161,6 +346,75 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             LinkTypes::ListNotes => {
                 validate_create_link_list_notes(action, base_address, target_address, tag)
             }
+            LinkTypes::AgentToNotes => {
+                validate_create_link_agent_to_notes(action, base_address, target_address, tag)
+            }
+            LinkTypes::NotesByTimePath => {
+                validate_create_link_notes_by_time_path(action, base_address, target_address, tag)
+            }
+            LinkTypes::NotesByTime => {
+                validate_create_link_notes_by_time(action, base_address, target_address, tag)
+            }
+            LinkTypes::AllTags => {
+                validate_create_link_all_tags(action, base_address, target_address, tag)
+            }
+            LinkTypes::TagToNotes => {
+                validate_create_link_tag_to_notes(action, base_address, target_address, tag)
+            }
+            LinkTypes::EncryptedNoteUpdates => validate_create_link_encrypted_note_updates(
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToEncryptedNotes => validate_create_link_agent_to_encrypted_notes(
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToEncryptionKey => validate_create_link_agent_to_encryption_key(
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::SharedWith => {
+                validate_create_link_shared_with(action, base_address, target_address, tag)
+            }
+            LinkTypes::Subscribers => {
+                validate_create_link_subscribers(action, base_address, target_address, tag)
+            }
+            LinkTypes::Subscriptions => {
+                validate_create_link_subscriptions(action, base_address, target_address, tag)
+            }
+            LinkTypes::AgentToTrash => {
+                validate_create_link_agent_to_trash(action, base_address, target_address, tag)
+            }
+            LinkTypes::TermToNotes => {
+                validate_create_link_term_to_notes(action, base_address, target_address, tag)
+            }
+            LinkTypes::NotebookUpdates => {
+                validate_create_link_notebook_updates(action, base_address, target_address, tag)
+            }
+            LinkTypes::NotebookToChildren => {
+                validate_create_link_notebook_to_children(action, base_address, target_address, tag)
+            }
+            LinkTypes::NotebookToNotes => {
+                validate_create_link_notebook_to_notes(action, base_address, target_address, tag)
+            }
+            LinkTypes::NoteToAttachments => {
+                validate_create_link_note_to_attachments(action, base_address, target_address, tag)
+            }
+            LinkTypes::NoteReferences => {
+                validate_create_link_note_references(action, base_address, target_address, tag)
+            }
+            LinkTypes::NoteBacklinks => {
+                validate_create_link_note_backlinks(action, base_address, target_address, tag)
+            }
+            LinkTypes::TitleToNotes => {
+                validate_create_link_title_to_notes(action, base_address, target_address, tag)
+            }
         },
         FlatOp::RegisterDeleteLink {
             link_type,
@@ -184,6 +438,146 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::AgentToNotes => validate_delete_link_agent_to_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NotesByTimePath => validate_delete_link_notes_by_time_path(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NotesByTime => validate_delete_link_notes_by_time(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AllTags => validate_delete_link_all_tags(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::TagToNotes => validate_delete_link_tag_to_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::EncryptedNoteUpdates => validate_delete_link_encrypted_note_updates(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToEncryptedNotes => validate_delete_link_agent_to_encrypted_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToEncryptionKey => validate_delete_link_agent_to_encryption_key(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::SharedWith => validate_delete_link_shared_with(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::Subscribers => validate_delete_link_subscribers(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::Subscriptions => validate_delete_link_subscriptions(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToTrash => validate_delete_link_agent_to_trash(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::TermToNotes => validate_delete_link_term_to_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NotebookUpdates => validate_delete_link_notebook_updates(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NotebookToChildren => validate_delete_link_notebook_to_children(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NotebookToNotes => validate_delete_link_notebook_to_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NoteToAttachments => validate_delete_link_note_to_attachments(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NoteReferences => validate_delete_link_note_references(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NoteBacklinks => validate_delete_link_note_backlinks(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::TitleToNotes => validate_delete_link_title_to_notes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
         },
         FlatOp::StoreRecord(store_record) => {
             match store_record {
@@ -194,6 +588,25 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     EntryTypes::Note(note) => {
                         validate_create_note(EntryCreationAction::Create(action), note)
                     }
+                    EntryTypes::EncryptedNote(encrypted_note) => validate_create_encrypted_note(
+                        EntryCreationAction::Create(action),
+                        encrypted_note,
+                    ),
+                    EntryTypes::Notebook(notebook) => {
+                        validate_create_notebook(EntryCreationAction::Create(action), notebook)
+                    }
+                    EntryTypes::AttachmentChunk(attachment_chunk) => {
+                        validate_create_attachment_chunk(
+                            EntryCreationAction::Create(action),
+                            attachment_chunk,
+                        )
+                    }
+                    EntryTypes::AttachmentManifest(attachment_manifest) => {
+                        validate_create_attachment_manifest(
+                            EntryCreationAction::Create(action),
+                            attachment_manifest,
+                        )
+                    }
                 },
                 // Complementary validation to the `RegisterUpdate` Op, in which the record itself is validated
                 // If you want to optimize performance, you can remove the validation for an entry type here and keep it in `StoreEntry` and in `RegisterUpdate`
@@ -243,6 +656,134 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 Ok(result)
                             }
                         }
+                        EntryTypes::EncryptedNote(encrypted_note) => {
+                            let result = validate_create_encrypted_note(
+                                EntryCreationAction::Update(action.clone()),
+                                encrypted_note.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_encrypted_note: Option<EncryptedNote> =
+                                    original_record
+                                        .entry()
+                                        .to_app_option()
+                                        .map_err(|e| wasm_error!(e))?;
+                                let original_encrypted_note = match original_encrypted_note {
+                                    Some(encrypted_note) => encrypted_note,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_encrypted_note(
+                                    action,
+                                    encrypted_note,
+                                    original_action,
+                                    original_encrypted_note,
+                                )
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::Notebook(notebook) => {
+                            let result = validate_create_notebook(
+                                EntryCreationAction::Update(action.clone()),
+                                notebook.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_notebook: Option<Notebook> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let original_notebook = match original_notebook {
+                                    Some(notebook) => notebook,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_notebook(
+                                    action,
+                                    notebook,
+                                    original_action,
+                                    original_notebook,
+                                )
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::AttachmentChunk(attachment_chunk) => {
+                            let result = validate_create_attachment_chunk(
+                                EntryCreationAction::Update(action.clone()),
+                                attachment_chunk.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_attachment_chunk: Option<AttachmentChunk> =
+                                    original_record
+                                        .entry()
+                                        .to_app_option()
+                                        .map_err(|e| wasm_error!(e))?;
+                                let original_attachment_chunk = match original_attachment_chunk {
+                                    Some(attachment_chunk) => attachment_chunk,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_attachment_chunk(
+                                    action,
+                                    attachment_chunk,
+                                    original_action,
+                                    original_attachment_chunk,
+                                )
+                            } else {
+                                Ok(result)
+                            }
+                        }
+                        EntryTypes::AttachmentManifest(attachment_manifest) => {
+                            let result = validate_create_attachment_manifest(
+                                EntryCreationAction::Update(action.clone()),
+                                attachment_manifest.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_attachment_manifest: Option<AttachmentManifest> =
+                                    original_record
+                                        .entry()
+                                        .to_app_option()
+                                        .map_err(|e| wasm_error!(e))?;
+                                let original_attachment_manifest =
+                                    match original_attachment_manifest {
+                                        Some(attachment_manifest) => attachment_manifest,
+                                        None => {
+                                            return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                        }
+                                    };
+                                validate_update_attachment_manifest(
+                                    action,
+                                    attachment_manifest,
+                                    original_action,
+                                    original_attachment_manifest,
+                                )
+                            } else {
+                                Ok(result)
+                            }
+                        }
                     }
                 }
                 // Complementary validation to the `RegisterDelete` Op, in which the record itself is validated
@@ -298,6 +839,30 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         EntryTypes::Note(original_note) => {
                             validate_delete_note(action, original_action, original_note)
                         }
+                        EntryTypes::EncryptedNote(original_encrypted_note) => {
+                            validate_delete_encrypted_note(
+                                action,
+                                original_action,
+                                original_encrypted_note,
+                            )
+                        }
+                        EntryTypes::Notebook(original_notebook) => {
+                            validate_delete_notebook(action, original_action, original_notebook)
+                        }
+                        EntryTypes::AttachmentChunk(original_attachment_chunk) => {
+                            validate_delete_attachment_chunk(
+                                action,
+                                original_action,
+                                original_attachment_chunk,
+                            )
+                        }
+                        EntryTypes::AttachmentManifest(original_attachment_manifest) => {
+                            validate_delete_attachment_manifest(
+                                action,
+                                original_action,
+                                original_attachment_manifest,
+                            )
+                        }
                     }
                 }
                 // Complementary validation to the `RegisterCreateLink` Op, in which the record itself is validated
@@ -316,6 +881,118 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     LinkTypes::ListNotes => {
                         validate_create_link_list_notes(action, base_address, target_address, tag)
                     }
+                    LinkTypes::AgentToNotes => validate_create_link_agent_to_notes(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NotesByTimePath => validate_create_link_notes_by_time_path(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NotesByTime => validate_create_link_notes_by_time(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::AllTags => {
+                        validate_create_link_all_tags(action, base_address, target_address, tag)
+                    }
+                    LinkTypes::TagToNotes => {
+                        validate_create_link_tag_to_notes(action, base_address, target_address, tag)
+                    }
+                    LinkTypes::EncryptedNoteUpdates => validate_create_link_encrypted_note_updates(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::AgentToEncryptedNotes => {
+                        validate_create_link_agent_to_encrypted_notes(
+                            action,
+                            base_address,
+                            target_address,
+                            tag,
+                        )
+                    }
+                    LinkTypes::AgentToEncryptionKey => {
+                        validate_create_link_agent_to_encryption_key(
+                            action,
+                            base_address,
+                            target_address,
+                            tag,
+                        )
+                    }
+                    LinkTypes::SharedWith => {
+                        validate_create_link_shared_with(action, base_address, target_address, tag)
+                    }
+                    LinkTypes::Subscribers => {
+                        validate_create_link_subscribers(action, base_address, target_address, tag)
+                    }
+                    LinkTypes::Subscriptions => validate_create_link_subscriptions(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::AgentToTrash => validate_create_link_agent_to_trash(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::TermToNotes => validate_create_link_term_to_notes(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NotebookUpdates => validate_create_link_notebook_updates(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NotebookToChildren => validate_create_link_notebook_to_children(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NotebookToNotes => validate_create_link_notebook_to_notes(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NoteToAttachments => validate_create_link_note_to_attachments(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NoteReferences => validate_create_link_note_references(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NoteBacklinks => validate_create_link_note_backlinks(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::TitleToNotes => validate_create_link_title_to_notes(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
                 },
                 // Complementary validation to the `RegisterDeleteLink` Op, in which the record itself is validated
                 // If you want to optimize performance, you can remove the validation for an entry type here and keep it in `RegisterDeleteLink`
@@ -359,6 +1036,152 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             create_link.target_address,
                             create_link.tag,
                         ),
+                        LinkTypes::AgentToNotes => validate_delete_link_agent_to_notes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NotesByTimePath => validate_delete_link_notes_by_time_path(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NotesByTime => validate_delete_link_notes_by_time(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::AllTags => validate_delete_link_all_tags(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::TagToNotes => validate_delete_link_tag_to_notes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::EncryptedNoteUpdates => {
+                            validate_delete_link_encrypted_note_updates(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToEncryptedNotes => {
+                            validate_delete_link_agent_to_encrypted_notes(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::AgentToEncryptionKey => {
+                            validate_delete_link_agent_to_encryption_key(
+                                action,
+                                create_link.clone(),
+                                base_address,
+                                create_link.target_address,
+                                create_link.tag,
+                            )
+                        }
+                        LinkTypes::SharedWith => validate_delete_link_shared_with(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::Subscribers => validate_delete_link_subscribers(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::Subscriptions => validate_delete_link_subscriptions(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::AgentToTrash => validate_delete_link_agent_to_trash(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::TermToNotes => validate_delete_link_term_to_notes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NotebookUpdates => validate_delete_link_notebook_updates(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NotebookToChildren => validate_delete_link_notebook_to_children(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NotebookToNotes => validate_delete_link_notebook_to_notes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NoteToAttachments => validate_delete_link_note_to_attachments(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NoteReferences => validate_delete_link_note_references(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NoteBacklinks => validate_delete_link_note_backlinks(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::TitleToNotes => validate_delete_link_title_to_notes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
                     }
                 }
                 OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
@@ -6,48 +6,335 @@ pub struct Note {
     pub title: String,
     pub content: String,
     pub created_at: Timestamp,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Agents other than the owner who are allowed to push revisions of the note
+    #[serde(default)]
+    pub editors: Vec<AgentPubKey>,
+    // Notes created before formats were introduced are plain text
+    #[serde(default)]
+    pub format: crate::NoteFormat,
+    // Set on the revisions editors write: the head of the owner's chain when they wrote it,
+    // against which validation checks that the owner had not removed them yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_chain_head: Option<ActionHash>,
+}
+
+// Tag of the NoteUpdates link of a merge, listing the heads it merges besides the revision it updates
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+pub struct MergedHeads(pub Vec<ActionHash>);
+
+impl MergedHeads {
+    pub fn from_tag(tag: LinkTag) -> ExternResult<Self> {
+        if tag.0.is_empty() {
+            return Ok(MergedHeads(vec![]));
+        }
+        MergedHeads::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner())))
+            .map_err(|e| wasm_error!(e))
+    }
+}
+
+// Follows the chain of updates back to the action that created the note, returning its hash and author
+pub fn resolve_original_note(action_hash: ActionHash) -> ExternResult<(ActionHash, AgentPubKey)> {
+    let mut action_hash = action_hash;
+    loop {
+        let action = must_get_action(action_hash.clone())?;
+        match action.action() {
+            Action::Update(update) => action_hash = update.original_action_address.clone(),
+            action => return Ok((action_hash, action.author().clone())),
+        }
+    }
+}
+
+// The owner of a note is the author of its original Create action
+pub fn note_owner(action: &EntryCreationAction) -> ExternResult<AgentPubKey> {
+    match action {
+        EntryCreationAction::Create(create) => Ok(create.author.clone()),
+        EntryCreationAction::Update(update) => {
+            Ok(resolve_original_note(update.original_action_address.clone())?.1)
+        }
+    }
+}
+
+// Whether the revision is `ancestor` or was built on it, following the chain of updates back
+pub fn is_built_on(revision: ActionHash, ancestor: &ActionHash) -> ExternResult<bool> {
+    let mut action_hash = revision;
+    loop {
+        if action_hash == *ancestor {
+            return Ok(true);
+        }
+        match must_get_action(action_hash)?.action() {
+            Action::Update(update) => action_hash = update.original_action_address.clone(),
+            _ => return Ok(false),
+        }
+    }
+}
+
+// The revisions of the note its owner had written as of `owner_chain_head`, oldest first, found from the
+// NoteUpdates links on the owner's chain, or None if the owner had not created the note by then
+pub fn owner_revisions(
+    original_note_hash: &ActionHash,
+    owner: AgentPubKey,
+    owner_chain_head: ActionHash,
+) -> ExternResult<Option<Vec<ActionHash>>> {
+    let note_updates = ScopedLinkType::try_from(crate::LinkTypes::NoteUpdates)?;
+    let base: AnyLinkableHash = original_note_hash.clone().into();
+    let mut activity = must_get_agent_activity(
+        owner,
+        ChainFilter::new(owner_chain_head).until(original_note_hash.clone()),
+    )?;
+    if !activity
+        .iter()
+        .any(|activity| activity.action.hashed.hash == *original_note_hash)
+    {
+        return Ok(None);
+    }
+    activity.sort_by_key(|activity| activity.action.action().action_seq());
+    let mut revisions = vec![original_note_hash.clone()];
+    for activity in activity {
+        let Action::CreateLink(create_link) = activity.action.action() else {
+            continue;
+        };
+        if create_link.zome_index != note_updates.zome_index
+            || create_link.link_type != note_updates.zome_type
+            || create_link.base_address != base
+        {
+            continue;
+        }
+        if let Some(target) = create_link.target_address.clone().into_action_hash() {
+            revisions.push(target);
+        }
+    }
+    Ok(Some(revisions))
+}
+
+// The revisions of the note its owner had written as of the chain head an editor names, oldest first,
+// provided the newest of them still lists the editor, or None if the owner had removed them by then
+pub fn editor_grant(
+    editor: &AgentPubKey,
+    owner: AgentPubKey,
+    original_note_hash: &ActionHash,
+    owner_chain_head: Option<ActionHash>,
+) -> ExternResult<Option<Vec<ActionHash>>> {
+    let Some(owner_chain_head) = owner_chain_head else {
+        return Ok(None);
+    };
+    let Some(revisions) = owner_revisions(original_note_hash, owner, owner_chain_head)? else {
+        return Ok(None);
+    };
+    let Some(newest) = revisions.last() else {
+        return Ok(None);
+    };
+    let newest_note: Note = must_get_valid_record(newest.clone())?
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if !newest_note.editors.contains(editor) {
+        return Ok(None);
+    }
+    Ok(Some(revisions))
+}
+
+// Whether the agent may maintain the index links of the note as of `prev_action`: its owner, or an editor
+// whose newest revision of the note on their chain was written while the owner still listed them
+pub fn may_index_note(
+    agent: &AgentPubKey,
+    prev_action: ActionHash,
+    original_note_hash: &ActionHash,
+) -> ExternResult<bool> {
+    let owner = must_get_action(original_note_hash.clone())?
+        .action()
+        .author()
+        .clone();
+    if *agent == owner {
+        return Ok(true);
+    }
+    let note_updates = ScopedLinkType::try_from(crate::LinkTypes::NoteUpdates)?;
+    let base: AnyLinkableHash = original_note_hash.clone().into();
+    let revision = must_get_agent_activity(agent.clone(), ChainFilter::new(prev_action))?
+        .into_iter()
+        .filter_map(|activity| match activity.action.action() {
+            Action::CreateLink(create_link)
+                if create_link.zome_index == note_updates.zome_index
+                    && create_link.link_type == note_updates.zome_type
+                    && create_link.base_address == base =>
+            {
+                Some((
+                    create_link.action_seq,
+                    create_link.target_address.clone().into_action_hash(),
+                ))
+            }
+            _ => None,
+        })
+        .max_by_key(|(action_seq, _)| *action_seq)
+        .and_then(|(_, revision)| revision);
+    let Some(revision) = revision else {
+        return Ok(false);
+    };
+    let record = must_get_valid_record(revision)?;
+    if record.action().author() != agent {
+        return Ok(false);
+    }
+    let Some(note) = record
+        .entry()
+        .to_app_option::<Note>()
+        .map_err(|e| wasm_error!(e))?
+    else {
+        return Ok(false);
+    };
+    Ok(editor_grant(agent, owner, original_note_hash, note.owner_chain_head)?.is_some())
+}
+
+// Whether the author of a DeleteLink may maintain the index links of the note at the given end of the link
+pub fn may_index_linked_note(
+    action: &DeleteLink,
+    original_note_address: AnyLinkableHash,
+) -> ExternResult<bool> {
+    let Some(original_note_hash) = original_note_address.into_action_hash() else {
+        return Ok(false);
+    };
+    may_index_note(
+        &action.author,
+        action.prev_action.clone(),
+        &original_note_hash,
+    )
+}
+
+// Index links pointing to a note are tagged with the note's hash, see `get_links_to_note`
+pub fn is_note_link_tag(tag: &LinkTag, original_note_hash: &ActionHash) -> bool {
+    tag.0 == original_note_hash.get_raw_39()
 }
 
 pub fn validate_create_note(
-    _action: EntryCreationAction,
-    _note: Note,
+    action: EntryCreationAction,
+    note: Note,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    let properties = crate::notes_properties()?;
+    if note.title.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Note title cannot be empty".to_string(),
+        ));
+    }
+    if note.title.chars().count() > properties.max_title_length {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Note title cannot be longer than {} characters",
+            properties.max_title_length
+        )));
+    }
+    if note.content.len() > properties.max_content_bytes {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Note content cannot be larger than {} bytes",
+            properties.max_content_bytes
+        )));
+    }
+    if let Some(error) = crate::validate_note_format(note.format, &note.content) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    if let Some(error) = crate::validate_tags(&note.tags) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    for (index, editor) in note.editors.iter().enumerate() {
+        if note.editors[..index].contains(editor) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Editor {editor} is duplicated"
+            )));
+        }
+    }
+    if note.editors.contains(&note_owner(&action)?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The owner of a note cannot be one of its editors".to_string(),
+        ));
+    }
+    // Updates keep the created_at of the original note, which validate_update_note checks
+    if let EntryCreationAction::Create(create) = action {
+        // created_at is chosen by the author, so the difference must not overflow
+        let drift = note
+            .created_at
+            .as_micros()
+            .abs_diff(create.timestamp.as_micros());
+        let tolerance = u64::try_from(properties.created_at_tolerance_seconds)
+            .unwrap_or(0)
+            .saturating_mul(1_000_000);
+        if drift > tolerance {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Note created_at must be within {} seconds of the action timestamp",
+                properties.created_at_tolerance_seconds
+            )));
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_update_note(
-    _action: Update,
-    _note: Note,
-    _original_action: EntryCreationAction,
-    _original_note: Note,
+    action: Update,
+    note: Note,
+    original_action: EntryCreationAction,
+    original_note: Note,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    // Editors are checked against the owner's newest revision as of the chain head the editor names,
+    // so that once removed they cannot update an older revision that still lists them
+    let owner = note_owner(&original_action)?;
+    if action.author != owner
+        && editor_grant(
+            &action.author,
+            owner.clone(),
+            &resolve_original_note(action.original_action_address.clone())?.0,
+            note.owner_chain_head.clone(),
+        )?
+        .is_none()
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can update it".to_string(),
+        ));
+    }
+    if action.author != owner && note.editors != original_note.editors {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can change its editors".to_string(),
+        ));
+    }
+    // The tag index is maintained by the owner alone
+    if action.author != owner && note.tags != original_note.tags {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can change its tags".to_string(),
+        ));
+    }
+    if note.created_at != original_note.created_at {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The created_at of a note cannot be changed".to_string(),
+        ));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_delete_note(
-    _action: Delete,
-    _original_action: EntryCreationAction,
+    action: Delete,
+    original_action: EntryCreationAction,
     _original_note: Note,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    if action.author != note_owner(&original_action)? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can delete it".to_string(),
+        ));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_create_link_note_updates(
-    _action: CreateLink,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
-    _tag: LinkTag,
+    tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
     let action_hash = base_address
         .into_action_hash()
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "No action hash associated with link".to_string()
         )))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _note: crate::Note = record
+    let original_record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = original_record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
@@ -60,15 +347,74 @@ pub fn validate_create_link_note_updates(
             .ok_or(wasm_error!(WasmErrorInner::Guest(
                 "No action hash associated with link".to_string()
             )))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _note: crate::Note = record
+    let updated_record = must_get_valid_record(action_hash.clone())?;
+    let updated_note: crate::Note = updated_record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    // TODO: add the appropriate validation rules
+    // The update itself was validated against the owner and editors of the note
+    if action.author != *updated_record.action().author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an update can link it to the note".to_string(),
+        ));
+    }
+    if resolve_original_note(action_hash.clone())?.0 != *original_record.action_address() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NoteUpdates link must point to an update of the note it hangs off".to_string(),
+        ));
+    }
+    let Ok(merged_heads) = MergedHeads::from_tag(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NoteUpdates link must list the heads it merges".to_string(),
+        ));
+    };
+    let original_note_hash = original_record.action_address();
+    let owner = original_record.action().author().clone();
+    for head in merged_heads.0.iter() {
+        if *head == action_hash || resolve_original_note(head.clone())?.0 != *original_note_hash {
+            return Ok(ValidateCallbackResult::Invalid(
+                "A merge can only merge other revisions of the same note".to_string(),
+            ));
+        }
+    }
+    // A merged head cannot be a revision that the merge, or another merged head, was already built on
+    let mut revisions: Vec<ActionHash> = merged_heads.0.clone();
+    revisions.push(action_hash.clone());
+    for head in merged_heads.0.iter() {
+        for revision in revisions.iter().filter(|revision| *revision != head) {
+            if is_built_on(revision.clone(), head)? {
+                return Ok(ValidateCallbackResult::Invalid(format!(
+                    "{head} is not a head of the note"
+                )));
+            }
+        }
+    }
+    // Editors can only supersede the revisions of the owner they could see when writing the merge
+    if action.author != owner {
+        let Some(owner_revisions) = editor_grant(
+            &action.author,
+            owner.clone(),
+            original_note_hash,
+            updated_note.owner_chain_head,
+        )?
+        else {
+            return Ok(ValidateCallbackResult::Invalid(
+                "Only the owner or a current editor of a note can merge its revisions".to_string(),
+            ));
+        };
+        for head in merged_heads.0.iter() {
+            if *must_get_action(head.clone())?.action().author() == owner
+                && !owner_revisions.contains(head)
+            {
+                return Ok(ValidateCallbackResult::Invalid(format!(
+                    "{head} was not yet written by the owner of the note as of this merge"
+                )));
+            }
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
@@ -109,12 +455,16 @@ pub fn validate_create_link_list_notes(
 }
 
 pub fn validate_delete_link_list_notes(
-    _action: DeleteLink,
-    _original_action: CreateLink,
+    action: DeleteLink,
+    original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a note can remove it from the list".to_string(),
+        ));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
@@ -0,0 +1,307 @@
+use hdi::prelude::*;
+use std::collections::BTreeMap;
+
+pub const MAX_NOTEBOOK_NAME_LENGTH: usize = 100;
+
+// A folder of notes, nested in the notebook whose original action hash is `parent`, if any
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct Notebook {
+    pub name: String,
+    pub parent: Option<ActionHash>,
+}
+
+// Maps the original action hash of each notebook the agent had committed before `prev_action`
+// to the action hash of its latest revision at that point
+// Notebooks can only be nested in those of the same author, so their own chain holds the whole tree
+fn latest_notebook_revisions(
+    author: AgentPubKey,
+    prev_action: ActionHash,
+) -> ExternResult<BTreeMap<ActionHash, ActionHash>> {
+    let notebook_entry_type: EntryType = crate::UnitEntryTypes::Notebook.try_into()?;
+    let mut activity = must_get_agent_activity(author, ChainFilter::new(prev_action))?;
+    activity.sort_by_key(|activity| activity.action.action().action_seq());
+    let mut originals: BTreeMap<ActionHash, ActionHash> = BTreeMap::new();
+    let mut latest: BTreeMap<ActionHash, ActionHash> = BTreeMap::new();
+    for activity in activity {
+        let hash = activity.action.as_hash().clone();
+        match activity.action.action() {
+            Action::Create(create) if create.entry_type == notebook_entry_type => {
+                originals.insert(hash.clone(), hash.clone());
+                latest.insert(hash.clone(), hash);
+            }
+            Action::Update(update) if update.entry_type == notebook_entry_type => {
+                if let Some(original) = originals.get(&update.original_action_address).cloned() {
+                    originals.insert(hash.clone(), original.clone());
+                    latest.insert(original, hash);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(latest)
+}
+
+// Walks up from the parent of the notebook through the latest revision of each ancestor,
+// returning an error message if the notebook would end up inside itself or in another author's notebook
+fn validate_notebook_parent(
+    action: &EntryCreationAction,
+    notebook: &Notebook,
+) -> ExternResult<Option<String>> {
+    let Some(parent) = notebook.parent.clone() else {
+        return Ok(None);
+    };
+    let original_notebook_hash = match action {
+        EntryCreationAction::Create(_) => None,
+        EntryCreationAction::Update(update) => Some(resolve_original_notebook(
+            update.original_action_address.clone(),
+        )?),
+    };
+    let prev_action = match action {
+        EntryCreationAction::Create(create) => create.prev_action.clone(),
+        EntryCreationAction::Update(update) => update.prev_action.clone(),
+    };
+    let latest = latest_notebook_revisions(action.author().clone(), prev_action)?;
+    let mut ancestor = Some(parent);
+    let mut depth = 0;
+    while let Some(ancestor_hash) = ancestor {
+        if Some(&ancestor_hash) == original_notebook_hash.as_ref() {
+            return Ok(Some(
+                "A notebook cannot be moved inside itself or one of its descendants".to_string(),
+            ));
+        }
+        let Some(revision_hash) = latest.get(&ancestor_hash) else {
+            return Ok(Some(
+                "Notebooks can only be nested in notebooks of the same author".to_string(),
+            ));
+        };
+        // Each ancestor is visited once unless the tree already has a cycle
+        depth += 1;
+        if depth > latest.len() {
+            return Ok(Some("The notebook tree has a cycle".to_string()));
+        }
+        let record = must_get_valid_record(revision_hash.clone())?;
+        let ancestor_notebook: Notebook = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "Linked action must reference an entry".to_string()
+            )))?;
+        ancestor = ancestor_notebook.parent;
+    }
+    Ok(None)
+}
+
+// Follows the chain of updates back to the action that created the notebook
+pub fn resolve_original_notebook(action_hash: ActionHash) -> ExternResult<ActionHash> {
+    let mut action_hash = action_hash;
+    loop {
+        let action = must_get_action(action_hash.clone())?;
+        match action.action() {
+            Action::Update(update) => action_hash = update.original_action_address.clone(),
+            _ => return Ok(action_hash),
+        }
+    }
+}
+
+pub fn validate_create_notebook(
+    action: EntryCreationAction,
+    notebook: Notebook,
+) -> ExternResult<ValidateCallbackResult> {
+    if notebook.name.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Notebook name cannot be empty".to_string(),
+        ));
+    }
+    if notebook.name.chars().count() > MAX_NOTEBOOK_NAME_LENGTH {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Notebook name cannot be longer than {MAX_NOTEBOOK_NAME_LENGTH} characters"
+        )));
+    }
+    if let Some(error) = validate_notebook_parent(&action, &notebook)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_notebook(
+    action: Update,
+    _notebook: Notebook,
+    original_action: EntryCreationAction,
+    _original_notebook: Notebook,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a notebook can update it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_notebook(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_notebook: Notebook,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a notebook can delete it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// The base and target of notebook links must be original notebook actions by the link author
+fn validate_own_notebook(
+    action: &CreateLink,
+    address: AnyLinkableHash,
+) -> ExternResult<Option<String>> {
+    let action_hash = address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _notebook: crate::Notebook = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let Action::Create(create) = record.action() else {
+        return Ok(Some(
+            "Notebook links must point to the original action of a notebook".to_string(),
+        ));
+    };
+    if create.author != action.author {
+        return Ok(Some(
+            "Only the author of a notebook can link it".to_string(),
+        ));
+    }
+    Ok(None)
+}
+
+pub fn validate_create_link_notebook_updates(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = validate_own_notebook(&action, base_address.clone())? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let updated_record = must_get_valid_record(action_hash.clone())?;
+    let _notebook: crate::Notebook = updated_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if base_address.into_action_hash() != Some(resolve_original_notebook(action_hash)?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NotebookUpdates link must point to an update of the notebook it hangs off"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_notebook_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "NotebookUpdates links cannot be deleted".to_string(),
+    ))
+}
+
+// Links a notebook, or the AgentPubKey of its author for top-level notebooks, to the notebooks nested in it
+pub fn validate_create_link_notebook_to_children(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.clone().into_agent_pub_key() != Some(action.author.clone()) {
+        if let Some(error) = validate_own_notebook(&action, base_address)? {
+            return Ok(ValidateCallbackResult::Invalid(error));
+        }
+    }
+    if let Some(error) = validate_own_notebook(&action, target_address)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_notebook_to_children(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a notebook can move the notebooks in it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_notebook_to_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = validate_own_notebook(&action, base_address)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NotebookToNotes link must point to the original action of a note".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_notebook_to_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a notebook can move the notes in it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,42 @@
+use hdi::prelude::*;
+
+// Limits that can be tuned per network through the DNA properties
+// Any property that isn't set falls back to its default, and so do all of them when no properties are set
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+#[serde(default)]
+pub struct NotesProperties {
+    pub max_title_length: usize,
+    pub max_content_bytes: usize,
+    pub max_ciphertext_bytes: usize,
+    pub created_at_tolerance_seconds: i64,
+    // How long a note stays in the trash before `purge_expired_trash` deletes it
+    pub trash_retention_seconds: i64,
+    pub max_attachment_bytes: u64,
+}
+
+impl Default for NotesProperties {
+    fn default() -> Self {
+        Self {
+            max_title_length: 200,
+            max_content_bytes: 1_000_000,
+            max_ciphertext_bytes: 1_100_000,
+            created_at_tolerance_seconds: 300,
+            trash_retention_seconds: 30 * 24 * 60 * 60,
+            max_attachment_bytes: 20 * 1024 * 1024,
+        }
+    }
+}
+
+// Properties that are set but malformed are an error rather than silently replaced by the defaults
+pub fn notes_properties() -> ExternResult<NotesProperties> {
+    let properties = dna_info()?.modifiers.properties;
+    // A DNA without properties has them serialized as empty bytes or a msgpack nil
+    if properties.bytes().is_empty() || properties.bytes().as_slice() == [0xc0] {
+        return Ok(NotesProperties::default());
+    }
+    NotesProperties::try_from(properties).map_err(|e| {
+        wasm_error!(WasmErrorInner::Guest(format!(
+            "Malformed DNA properties: {e:?}"
+        )))
+    })
+}
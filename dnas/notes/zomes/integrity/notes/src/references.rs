@@ -0,0 +1,261 @@
+use hdi::prelude::*;
+
+pub const MAX_REFERENCE_LENGTH: usize = 200;
+pub const NOTE_TITLES_ROOT: &str = "note_titles";
+
+// The `[[...]]` references in the content of a note, each one either the hash of a note or its title,
+// in order of first appearance and without duplicates
+pub fn note_references(note: &crate::Note) -> Vec<String> {
+    // The text fields of rich text are where its references are written
+    let text = match note.format {
+        crate::NoteFormat::RichText => crate::plain_text(note.format, &note.content),
+        _ => note.content.clone(),
+    };
+    let mut references: Vec<String> = vec![];
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else {
+            break;
+        };
+        let reference = rest[..end].trim();
+        // Unclosed brackets on a line don't swallow the following ones
+        if let Some(newline) = rest[..end].find('\n') {
+            rest = &rest[newline..];
+            continue;
+        }
+        if !reference.is_empty()
+            && reference.chars().count() <= MAX_REFERENCE_LENGTH
+            && !references.iter().any(|existing| existing == reference)
+        {
+            references.push(reference.to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+    references
+}
+
+// Titles are matched regardless of case and of the whitespace around and between their words
+pub fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+pub fn title_path(normalized_title: &str) -> Path {
+    Path::from(vec![
+        Component::from(NOTE_TITLES_ROOT),
+        Component::from(normalized_title),
+    ])
+}
+
+// The tag of a TitleToNotes link is the raw hash of the note followed by its UTF-8 normalized title,
+// so that the link of one note can be looked up by tag prefix
+pub fn title_link_tag(original_note_hash: &ActionHash, normalized_title: &str) -> LinkTag {
+    let mut tag = original_note_hash.get_raw_39().to_vec();
+    tag.extend_from_slice(normalized_title.as_bytes());
+    LinkTag::new(tag)
+}
+
+// Returns the Create action of the note, or None if the address is not the original action of a note
+fn must_get_original_note(address: AnyLinkableHash) -> ExternResult<Option<Create>> {
+    let action_hash = address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    match record.action() {
+        Action::Create(create) => Ok(Some(create.clone())),
+        _ => Ok(None),
+    }
+}
+
+// Links a note to the notes its content references, tagged with the referenced note's hash
+// Its owner and editors maintain the references of the referencing note, like its other index links
+pub fn validate_create_link_note_references(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(source_hash) = base_address.clone().into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "No action hash associated with link".to_string(),
+        ));
+    };
+    if must_get_original_note(base_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NoteReferences link must start from the original action of a note".to_string(),
+        ));
+    }
+    if !crate::may_index_note(&action.author, action.prev_action, &source_hash)? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can link the notes it references"
+                .to_string(),
+        ));
+    }
+    let Some(target_hash) = target_address.clone().into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "No action hash associated with link".to_string(),
+        ));
+    };
+    if must_get_original_note(target_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NoteReferences link must point to the original action of a note".to_string(),
+        ));
+    }
+    if tag.0 != target_hash.get_raw_39() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NoteReferences link must be the hash of the referenced note".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_note_references(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !crate::may_index_linked_note(&action, original_action.base_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can unlink the notes it references"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// The reverse of NoteReferences: links a referenced note to the notes referencing it,
+// tagged with the referencing note's hash
+pub fn validate_create_link_note_backlinks(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if must_get_original_note(base_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NoteBacklinks link must start from the original action of a note".to_string(),
+        ));
+    }
+    let Some(source_hash) = target_address.clone().into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "No action hash associated with link".to_string(),
+        ));
+    };
+    if must_get_original_note(target_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NoteBacklinks link must point to the original action of a note".to_string(),
+        ));
+    }
+    if !crate::may_index_note(&action.author, action.prev_action, &source_hash)? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can link it from the notes it references"
+                .to_string(),
+        ));
+    }
+    if tag.0 != source_hash.get_raw_39() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NoteBacklinks link must be the hash of the referencing note".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_note_backlinks(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !crate::may_index_linked_note(&action, original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can unlink it from the notes it references"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Links the path of a normalized title to the notes with that title, which is how references by title resolve
+// Its owner and editors index its title, like its search terms
+pub fn validate_create_link_title_to_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(note_hash) = target_address.clone().into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "No action hash associated with link".to_string(),
+        ));
+    };
+    if must_get_original_note(target_address)?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A TitleToNotes link must point to the original action of a note".to_string(),
+        ));
+    }
+    if !crate::may_index_note(&action.author, action.prev_action, &note_hash)? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can index its title".to_string(),
+        ));
+    }
+    let title = match tag
+        .into_inner()
+        .strip_prefix(note_hash.get_raw_39())
+        .map(|title| String::from_utf8(title.to_vec()))
+    {
+        Some(Ok(title)) => title,
+        _ => return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a TitleToNotes link must be the hash of the note followed by its UTF-8 title"
+                .to_string(),
+        )),
+    };
+    if title.is_empty() || normalize_title(&title) != title {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "\"{title}\" is not a normalized title"
+        )));
+    }
+    if base_address != AnyLinkableHash::from(title_path(&title).path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A TitleToNotes link must hang off the path of its title".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_title_to_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !crate::may_index_linked_note(&action, original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can remove its title from the index"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
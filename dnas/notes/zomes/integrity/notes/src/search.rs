@@ -0,0 +1,147 @@
+use hdi::prelude::*;
+
+pub const SEARCH_TERMS_ROOT: &str = "search_terms";
+pub const MIN_TERM_LENGTH: usize = 2;
+pub const MAX_TERM_LENGTH: usize = 32;
+// Bounds the number of links written for a single large note, later terms are not searchable
+pub const MAX_TERMS_PER_NOTE: usize = 1000;
+
+pub fn term_path(term: &str) -> Path {
+    Path::from(vec![
+        Component::from(SEARCH_TERMS_ROOT),
+        Component::from(term),
+    ])
+}
+
+// Splits text into lowercase alphanumeric terms, along with the char offset and char length of each in the text
+// Words too short or too long to be useful search terms are skipped
+pub fn tokenize_with_offsets(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = vec![];
+    let mut current: Option<(usize, String)> = None;
+    for (offset, c) in text.chars().chain(std::iter::once(' ')).enumerate() {
+        if c.is_alphanumeric() {
+            let (_, word) = current.get_or_insert_with(|| (offset, String::new()));
+            word.push(c);
+            continue;
+        }
+        if let Some((start, word)) = current.take() {
+            let length = word.chars().count();
+            // Lowercasing can add combining marks, such as the dot of "İ", which are not part of a term
+            let term: String = word
+                .to_lowercase()
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect();
+            if (MIN_TERM_LENGTH..=MAX_TERM_LENGTH).contains(&term.chars().count()) {
+                tokens.push((start, length, term));
+            }
+        }
+    }
+    tokens
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    tokenize_with_offsets(text)
+        .into_iter()
+        .map(|(_, _, term)| term)
+        .collect()
+}
+
+// The distinct terms of a note's title and the plain text of its content, in order of first appearance
+pub fn note_terms(note: &crate::Note) -> Vec<String> {
+    let mut terms: Vec<String> = vec![];
+    for term in tokenize(&note.title)
+        .into_iter()
+        .chain(tokenize(&crate::plain_text(note.format, &note.content)))
+    {
+        if terms.len() == MAX_TERMS_PER_NOTE {
+            break;
+        }
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+// The tag of a TermToNotes link is the raw hash of the note followed by the UTF-8 term,
+// so that the links of one note can be looked up by tag prefix
+pub fn term_link_tag(original_note_hash: &ActionHash, term: &str) -> LinkTag {
+    let mut tag = original_note_hash.get_raw_39().to_vec();
+    tag.extend_from_slice(term.as_bytes());
+    LinkTag::new(tag)
+}
+
+pub fn validate_create_link_term_to_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash.clone())?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A TermToNotes link must point to the original action of a note".to_string(),
+        ));
+    }
+    // Editors index the terms of the revisions they write, see `may_index_note`
+    if !crate::may_index_note(&action.author, action.prev_action, &action_hash)? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can index it for search".to_string(),
+        ));
+    }
+    let tag = tag.into_inner();
+    let prefix = action_hash.get_raw_39();
+    let term = match tag
+        .strip_prefix(prefix)
+        .map(|term| String::from_utf8(term.to_vec()))
+    {
+        Some(Ok(term)) => term,
+        _ => return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a TermToNotes link must be the hash of the note followed by the UTF-8 term"
+                .to_string(),
+        )),
+    };
+    if tokenize(&term) != vec![term.clone()] {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "\"{term}\" is not a normalized search term"
+        )));
+    }
+    if base_address != AnyLinkableHash::from(term_path(&term).path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A TermToNotes link must hang off the path of its term".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_term_to_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !crate::may_index_linked_note(&action, original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner or a current editor of a note can remove it from the search index"
+                .to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
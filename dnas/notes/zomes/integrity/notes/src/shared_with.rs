@@ -0,0 +1,90 @@
+use hdi::prelude::*;
+
+// The tag of a SharedWith link is the raw hash of the revision of the note that holds the recipient's key envelope
+pub fn shared_with_link_tag(envelope_revision_hash: &ActionHash) -> LinkTag {
+    LinkTag::new(envelope_revision_hash.get_raw_39())
+}
+
+// Links a recipient's AgentPubKey to an encrypted note whose author has sealed its content key to them
+pub fn validate_create_link_shared_with(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(recipient) = base_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The base of a SharedWith link must be the recipient's AgentPubKey".to_string(),
+        ));
+    };
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash.clone())?;
+    let _encrypted_note: crate::EncryptedNote = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of an encrypted note can share it".to_string(),
+        ));
+    }
+    let Ok(revision_hash) = ActionHash::try_from_raw_39(tag.into_inner()) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a SharedWith link must be the hash of a revision of the note".to_string(),
+        ));
+    };
+    let revision = must_get_valid_record(revision_hash.clone())?;
+    let revision_note: crate::EncryptedNote = revision
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if !revision_note
+        .envelopes
+        .iter()
+        .any(|envelope| envelope.recipient == recipient)
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A note can only be shared with an agent its content key is sealed to".to_string(),
+        ));
+    }
+    // Each revision updates the one before it, so following the updates back must lead to the note
+    let mut ancestor = revision.action().clone();
+    let mut ancestor_hash = revision_hash;
+    while let Action::Update(update) = ancestor {
+        ancestor_hash = update.original_action_address;
+        ancestor = must_get_action(ancestor_hash.clone())?.hashed.content;
+    }
+    if ancestor_hash != action_hash {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a SharedWith link must be the hash of a revision of the note".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Both the sharer and the recipient can remove a share
+pub fn validate_delete_link_shared_with(
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author && base.into_agent_pub_key() != Some(action.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author or the recipient of a share can remove it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,94 @@
+use hdi::prelude::*;
+
+// Subscriptions can follow either a note, by the hash of its original Create action, or an author
+fn validate_subscribable(address: AnyLinkableHash) -> ExternResult<Option<String>> {
+    if address.clone().into_agent_pub_key().is_some() {
+        return Ok(None);
+    }
+    let Some(action_hash) = address.into_action_hash() else {
+        return Ok(Some(
+            "Only notes and authors can be subscribed to".to_string(),
+        ));
+    };
+    let record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if !matches!(record.action(), Action::Create(_)) {
+        return Ok(Some(
+            "Subscriptions must point to the original action of a note".to_string(),
+        ));
+    }
+    Ok(None)
+}
+
+// Links a note or an author to the AgentPubKey of an agent who follows it
+pub fn validate_create_link_subscribers(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_agent_pub_key() != Some(action.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Agents can only subscribe themselves".to_string(),
+        ));
+    }
+    if let Some(error) = validate_subscribable(base_address)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_subscribers(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the subscriber can remove their subscription".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Links an agent's AgentPubKey to the notes and authors they follow
+pub fn validate_create_link_subscriptions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.into_agent_pub_key() != Some(action.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can link subscriptions off their own AgentPubKey"
+                .to_string(),
+        ));
+    }
+    if let Some(error) = validate_subscribable(target_address)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_subscriptions(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can remove links off their own AgentPubKey".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
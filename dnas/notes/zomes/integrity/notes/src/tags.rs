@@ -0,0 +1,168 @@
+use hdi::prelude::*;
+
+pub const ALL_TAGS_ROOT: &str = "all_tags";
+pub const MAX_TAGS_PER_NOTE: usize = 20;
+pub const MAX_TAG_LENGTH: usize = 50;
+
+// Built from components rather than a string, so that a tag containing the path delimiter stays a single component
+pub fn tag_path(tag: &str) -> Path {
+    Path::from(vec![Component::from(ALL_TAGS_ROOT), Component::from(tag)])
+}
+
+// Tags are stored trimmed and lowercased so that "Work" and " work" index together
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+// TagToNotes links are tagged with the hash of the note followed by the tag, so that the link from a tag
+// to a note can be found by tag prefix without reading every note with that tag
+pub fn note_tag_link_tag(original_note_hash: &ActionHash, tag: &str) -> LinkTag {
+    let mut link_tag = original_note_hash.get_raw_39().to_vec();
+    link_tag.extend_from_slice(tag.as_bytes());
+    LinkTag::new(link_tag)
+}
+
+pub fn validate_tags(tags: &[String]) -> Option<String> {
+    if tags.len() > MAX_TAGS_PER_NOTE {
+        return Some(format!(
+            "A note cannot have more than {MAX_TAGS_PER_NOTE} tags"
+        ));
+    }
+    for (index, tag) in tags.iter().enumerate() {
+        if tag.is_empty() {
+            return Some("Tags cannot be empty".to_string());
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Some(format!(
+                "Tags cannot be longer than {MAX_TAG_LENGTH} characters"
+            ));
+        }
+        if *tag != normalize_tag(tag) {
+            return Some(format!("Tag \"{tag}\" must be trimmed and lowercase"));
+        }
+        if tags[..index].contains(tag) {
+            return Some(format!("Tag \"{tag}\" is duplicated"));
+        }
+    }
+    None
+}
+
+// The string component that an hdk path link is tagged with, see `Path::make_tag`
+pub fn path_link_component(tag: &LinkTag) -> Option<String> {
+    let component =
+        Component::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0.clone()))).ok()?;
+    String::try_from(&component).ok()
+}
+
+// AllTags links are the links `TypedPath::ensure` creates for a tag path: one from the root of all paths
+// to the tags root, and one from the tags root to each tag
+pub fn validate_create_link_all_tags(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(component) = path_link_component(&tag) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of an AllTags link must be a path component".to_string(),
+        ));
+    };
+    let root = Path::from(vec![Component::from(ALL_TAGS_ROOT)]);
+    if base_address == hdi::hash_path::path::root_hash()? {
+        if component != ALL_TAGS_ROOT
+            || target_address != AnyLinkableHash::from(root.path_entry_hash()?)
+        {
+            return Ok(ValidateCallbackResult::Invalid(
+                "The only AllTags link from the root path must point to the tags root".to_string(),
+            ));
+        }
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if base_address != AnyLinkableHash::from(root.path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AllTags link must hang off the tags root".to_string(),
+        ));
+    }
+    if let Some(error) = validate_tags(std::slice::from_ref(&component)) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    if target_address != AnyLinkableHash::from(tag_path(&component).path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AllTags link must point to the path of the tag in its tag".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_all_tags(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "AllTags links cannot be deleted".to_string(),
+    ))
+}
+
+pub fn validate_create_link_tag_to_notes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash.clone())?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a note can tag it".to_string(),
+        ));
+    }
+    let tag = tag.into_inner();
+    let Some(Ok(note_tag)) = tag
+        .strip_prefix(action_hash.get_raw_39())
+        .map(|note_tag| String::from_utf8(note_tag.to_vec()))
+    else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a TagToNotes link must be the hash of the note followed by the UTF-8 note tag"
+                .to_string(),
+        ));
+    };
+    if let Some(error) = validate_tags(std::slice::from_ref(&note_tag)) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    if base_address != AnyLinkableHash::from(tag_path(&note_tag).path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A TagToNotes link must hang off the path of its tag".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_tag_to_notes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a note can untag it".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
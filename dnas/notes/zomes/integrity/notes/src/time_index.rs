@@ -0,0 +1,192 @@
+use hdi::prelude::*;
+
+pub const NOTES_BY_TIME_ROOT: &str = "notes_by_time";
+
+/// Splits a timestamp into zero-padded UTC `[year, month, day, hour]` bucket components,
+/// so that sorting the components as strings also sorts them chronologically
+pub fn time_bucket_components(timestamp: Timestamp) -> Vec<String> {
+    let seconds = timestamp.as_micros().div_euclid(1_000_000);
+    let days = seconds.div_euclid(86_400);
+    let hour = seconds.rem_euclid(86_400) / 3_600;
+    let (year, month, day) = civil_from_days(days);
+    vec![
+        format!("{year:04}"),
+        format!("{month:02}"),
+        format!("{day:02}"),
+        format!("{hour:02}"),
+    ]
+}
+
+/// The hour bucket path under which a note created at `timestamp` is indexed
+pub fn time_bucket_path(timestamp: Timestamp) -> Path {
+    time_bucket_prefix_path(&time_bucket_components(timestamp))
+}
+
+// NotesByTime links are tagged with the hash of the note followed by its creation time in big-endian
+// microseconds, so that links recreated when a note is restored still sort by when the note was created
+pub fn notes_by_time_link_tag(original_note_hash: &ActionHash, created_at: Timestamp) -> LinkTag {
+    let mut tag = original_note_hash.get_raw_39().to_vec();
+    tag.extend(created_at.as_micros().to_be_bytes());
+    LinkTag::new(tag)
+}
+
+// The creation time encoded in the tag of a NotesByTime link, if it has one
+pub fn notes_by_time_link_created_at(tag: &LinkTag) -> Option<Timestamp> {
+    let micros: [u8; 8] = tag.0.get(39..)?.try_into().ok()?;
+    Some(Timestamp::from_micros(i64::from_be_bytes(micros)))
+}
+
+// Converts days since the unix epoch into a (year, month, day) civil date
+// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The path of the time bucket made of the given leading `[year, month, day, hour]` components
+pub fn time_bucket_prefix_path(components: &[String]) -> Path {
+    let mut path = vec![Component::from(NOTES_BY_TIME_ROOT)];
+    path.extend(
+        components
+            .iter()
+            .map(|component| Component::from(component.as_str())),
+    );
+    Path::from(path)
+}
+
+// NotesByTimePath links go from a time bucket to each of its child buckets, and are tagged with the
+// components of the child joined by '/', e.g. "2024/05/17", so that validation can rebuild both paths
+pub fn time_bucket_link_tag(components: &[String]) -> LinkTag {
+    LinkTag::new(components.join("/").into_bytes())
+}
+
+// The components of the child bucket a NotesByTimePath link points to, or None if its tag is malformed
+pub fn parse_time_bucket_link_tag(tag: &LinkTag) -> Option<Vec<String>> {
+    let components: Vec<String> = std::str::from_utf8(&tag.0)
+        .ok()?
+        .split('/')
+        .map(String::from)
+        .collect();
+    is_time_bucket(&components).then_some(components)
+}
+
+// Checks that the components are the leading components of a bucket built by `time_bucket_components`
+fn is_time_bucket(components: &[String]) -> bool {
+    let formats: [(usize, u32, u32); 4] = [(4, 0, 9999), (2, 1, 12), (2, 1, 31), (2, 0, 23)];
+    !components.is_empty()
+        && components.len() <= formats.len()
+        && components
+            .iter()
+            .zip(formats)
+            .all(|(component, (width, min, max))| {
+                component.len() == width
+                    && component.bytes().all(|byte| byte.is_ascii_digit())
+                    && component
+                        .parse::<u32>()
+                        .is_ok_and(|value| (min..=max).contains(&value))
+            })
+}
+
+pub fn validate_create_link_notes_by_time_path(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(components) = parse_time_bucket_link_tag(&tag) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NotesByTimePath link must be the components of a time bucket, such as 2024/05/17"
+                .to_string(),
+        ));
+    };
+    let parent_hash =
+        time_bucket_prefix_path(&components[..components.len() - 1]).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(parent_hash) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NotesByTimePath link must start from the parent of the time bucket in its tag"
+                .to_string(),
+        ));
+    }
+    let child_hash = time_bucket_prefix_path(&components).path_entry_hash()?;
+    if target_address != AnyLinkableHash::from(child_hash) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NotesByTimePath link must point to the time bucket in its tag".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_notes_by_time_path(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "NotesByTimePath links cannot be deleted".to_string(),
+    ))
+}
+
+pub fn validate_create_link_notes_by_time(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash.clone())?;
+    if tag != notes_by_time_link_tag(&action_hash, record.action().timestamp()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NotesByTime link must be the hash of the note it points to followed by its creation time"
+                .to_string(),
+        ));
+    }
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a note can index it by time".to_string(),
+        ));
+    }
+    let bucket_hash = time_bucket_path(record.action().timestamp()).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(bucket_hash) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A note must be indexed under the time bucket of its creation action".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_notes_by_time(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a note can remove it from the time index".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,55 @@
+use hdi::prelude::*;
+
+// Links an agent's AgentPubKey to the notes they moved to the trash, the link timestamp being when they did so
+pub fn validate_create_link_agent_to_trash(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.into_agent_pub_key() != Some(action.author.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can link notes off their own AgentPubKey".to_string(),
+        ));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _note: crate::Note = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let Action::Create(create) = record.action() else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AgentToTrash link must point to the original action of a note".to_string(),
+        ));
+    };
+    if create.author != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the owner of a note can move it to the trash".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_trash(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can remove links off their own AgentPubKey".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
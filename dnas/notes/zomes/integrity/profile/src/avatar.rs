@@ -0,0 +1,81 @@
+use hdi::prelude::*;
+
+pub const MAX_AVATAR_BYTES: usize = 256 * 1024;
+// The image types an avatar can be, along with the magic bytes their content must start with
+// RIFF files also need "WEBP" at offset 8 to be webp images
+pub const AVATAR_MIME_TYPES: [(&str, &[u8]); 4] = [
+    ("image/png", b"\x89PNG\r\n\x1a\n"),
+    ("image/jpeg", b"\xff\xd8\xff"),
+    ("image/gif", b"GIF8"),
+    ("image/webp", b"RIFF"),
+];
+
+// An avatar image, stored on its own so that its entry hash addresses the content and identical images dedupe
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct Avatar {
+    pub mime_type: String,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+// Shared with the coordinator, so that callers get the reason before committing
+pub fn validate_avatar(avatar: &Avatar) -> Option<String> {
+    let Some((_, magic)) = AVATAR_MIME_TYPES
+        .iter()
+        .find(|(allowed, _)| *allowed == avatar.mime_type)
+    else {
+        return Some(format!(
+            "Avatar must be one of {}",
+            AVATAR_MIME_TYPES.map(|(mime_type, _)| mime_type).join(", ")
+        ));
+    };
+    if avatar.bytes.is_empty() {
+        return Some("Avatar cannot be empty".to_string());
+    }
+    if avatar.bytes.len() > MAX_AVATAR_BYTES {
+        return Some(format!(
+            "Avatar cannot be larger than {MAX_AVATAR_BYTES} bytes"
+        ));
+    }
+    let is_webp =
+        avatar.mime_type != "image/webp" || avatar.bytes.get(8..12) == Some(b"WEBP".as_slice());
+    if !avatar.bytes.starts_with(magic) || !is_webp {
+        return Some(format!(
+            "The content of the avatar is not of type {}",
+            avatar.mime_type
+        ));
+    }
+    None
+}
+
+pub fn validate_create_avatar(
+    _action: EntryCreationAction,
+    avatar: Avatar,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = validate_avatar(&avatar) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_avatar(
+    _action: Update,
+    _avatar: Avatar,
+    _original_action: EntryCreationAction,
+    _original_avatar: Avatar,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "Avatars cannot be updated, create a new one instead".to_string(),
+    ))
+}
+
+pub fn validate_delete_avatar(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_avatar: Avatar,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "Avatars cannot be deleted".to_string(),
+    ))
+}
@@ -1,5 +1,9 @@
+pub mod avatar;
+pub mod nickname_index;
 pub mod profile;
+pub use avatar::*;
 use hdi::prelude::*;
+pub use nickname_index::*;
 pub use profile::*;
 
 #[derive(Serialize, Deserialize)]
@@ -8,13 +12,17 @@ pub use profile::*;
 #[unit_enum(UnitEntryTypes)]
 pub enum EntryTypes {
     Profile(Profile),
+    Avatar(Avatar),
 }
+#[derive(Serialize, Deserialize)]
 #[hdk_link_types]
 pub enum LinkTypes {
     AgentToProfile,
+    NicknamePrefixes,
+    PrefixToAgents,
+    ProfileUpdates,
 }
 
-
 // Validation you perform during the genesis process. Nobody else on the network performs it, only you.
 // There *is no* access to network calls in this callback
 #[hdk_extern]
@@ -57,6 +65,9 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Profile(profile) => {
                     validate_create_profile(EntryCreationAction::Create(action), profile)
                 }
+                EntryTypes::Avatar(avatar) => {
+                    validate_create_avatar(EntryCreationAction::Create(action), avatar)
+                }
             },
             OpEntry::UpdateEntry {
                 app_entry, action, ..
@@ -64,6 +75,9 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Profile(profile) => {
                     validate_create_profile(EntryCreationAction::Update(action), profile)
                 }
+                EntryTypes::Avatar(avatar) => {
+                    validate_create_avatar(EntryCreationAction::Update(action), avatar)
+                }
             },
             _ => Ok(ValidateCallbackResult::Valid),
         },
@@ -99,6 +113,24 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             original_profile,
                         )
                     }
+                    EntryTypes::Avatar(avatar) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_avatar = match Avatar::try_from(original_app_entry) {
+                            Ok(entry) => entry,
+                            Err(e) => {
+                                return Ok(ValidateCallbackResult::Invalid(format!(
+                                    "Expected to get Avatar from Record: {e:?}"
+                                )));
+                            }
+                        };
+                        validate_update_avatar(
+                            action,
+                            avatar,
+                            original_create_action,
+                            original_avatar,
+                        )
+                    }
                 }
             }
             _ => Ok(ValidateCallbackResult::Valid),
@@ -148,14 +180,33 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     original_action,
                     original_profile,
                 ),
+                EntryTypes::Avatar(original_avatar) => validate_delete_avatar(
+                    delete_entry.clone().action,
+                    original_action,
+                    original_avatar,
+                ),
             }
         }
-        FlatOp::RegisterCreateLink { link_type, .. } => match link_type {
-            LinkTypes::AgentToProfile => Ok(ValidateCallbackResult::Valid),
-            _ => Ok(ValidateCallbackResult::Invalid(
-            "Only AgentToProfile links are allowed".to_string(),
-            )),
-        }   
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => match link_type {
+            LinkTypes::AgentToProfile => {
+                validate_create_link_agent_to_profile(action, base_address, target_address, tag)
+            }
+            LinkTypes::NicknamePrefixes => {
+                validate_create_link_nickname_prefixes(action, base_address, target_address, tag)
+            }
+            LinkTypes::PrefixToAgents => {
+                validate_create_link_prefix_to_agents(action, base_address, target_address, tag)
+            }
+            LinkTypes::ProfileUpdates => {
+                validate_create_link_profile_updates(action, base_address, target_address, tag)
+            }
+        },
         FlatOp::RegisterDeleteLink {
             link_type,
             base_address,
@@ -163,9 +214,36 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             tag,
             original_action,
             action,
-        } => Ok(ValidateCallbackResult::Invalid(
-            "There are no link types in this integrity zome".to_string(),
-        )),
+        } => match link_type {
+            LinkTypes::AgentToProfile => validate_delete_link_agent_to_profile(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::NicknamePrefixes => validate_delete_link_nickname_prefixes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::PrefixToAgents => validate_delete_link_prefix_to_agents(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::ProfileUpdates => validate_delete_link_profile_updates(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+        },
         FlatOp::StoreRecord(store_record) => {
             match store_record {
                 // Complementary validation to the `StoreEntry` Op, in which the record itself is validated
@@ -175,6 +253,9 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     EntryTypes::Profile(profile) => {
                         validate_create_profile(EntryCreationAction::Create(action), profile)
                     }
+                    EntryTypes::Avatar(avatar) => {
+                        validate_create_avatar(EntryCreationAction::Create(action), avatar)
+                    }
                 },
                 // Complementary validation to the `RegisterUpdate` Op, in which the record itself is validated
                 // If you want to optimize performance, you can remove the validation for an entry type here and keep it in `StoreEntry` and in `RegisterUpdate`
@@ -229,6 +310,37 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 Ok(result)
                             }
                         }
+                        EntryTypes::Avatar(avatar) => {
+                            let result = validate_create_avatar(
+                                EntryCreationAction::Update(action.clone()),
+                                avatar.clone(),
+                            )?;
+                            if let ValidateCallbackResult::Valid = result {
+                                let original_avatar: Option<Avatar> = original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                                let original_avatar = match original_avatar {
+                                    Some(avatar) => avatar,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                                validate_update_avatar(
+                                    action,
+                                    avatar,
+                                    original_action,
+                                    original_avatar,
+                                )
+                            } else {
+                                Ok(result)
+                            }
+                        }
                     }
                 }
                 // Complementary validation to the `RegisterDelete` Op, in which the record itself is validated
@@ -284,6 +396,9 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         EntryTypes::Profile(original_profile) => {
                             validate_delete_profile(action, original_action, original_profile)
                         }
+                        EntryTypes::Avatar(original_avatar) => {
+                            validate_delete_avatar(action, original_action, original_avatar)
+                        }
                     }
                 }
                 // Complementary validation to the `RegisterCreateLink` Op, in which the record itself is validated
@@ -295,9 +410,32 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     tag,
                     link_type,
                     action,
-                } => Ok(ValidateCallbackResult::Invalid(
-                    "There are no link types in this integrity zome".to_string(),
-                )),
+                } => match link_type {
+                    LinkTypes::AgentToProfile => validate_create_link_agent_to_profile(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::NicknamePrefixes => validate_create_link_nickname_prefixes(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::PrefixToAgents => validate_create_link_prefix_to_agents(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                    LinkTypes::ProfileUpdates => validate_create_link_profile_updates(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    ),
+                },
                 // Complementary validation to the `RegisterDeleteLink` Op, in which the record itself is validated
                 // If you want to optimize performance, you can remove the validation for an entry type here and keep it in `RegisterDeleteLink`
                 // Notice that doing so will cause `must_get_valid_record` for this record to return a valid record even if the `RegisterDeleteLink` validation failed
@@ -305,9 +443,57 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     original_action_hash,
                     base_address,
                     action,
-                } => Ok(ValidateCallbackResult::Invalid(
-                    "There are no link types in this integrity zome".to_string(),
-                )),
+                } => {
+                    let record = must_get_valid_record(original_action_hash)?;
+                    let create_link = match record.action() {
+                        Action::CreateLink(create_link) => create_link.clone(),
+                        _ => {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The action that a DeleteLink deletes must be a CreateLink"
+                                    .to_string(),
+                            ));
+                        }
+                    };
+                    let link_type = match LinkTypes::from_type(
+                        create_link.zome_index,
+                        create_link.link_type,
+                    )? {
+                        Some(lt) => lt,
+                        None => {
+                            return Ok(ValidateCallbackResult::Valid);
+                        }
+                    };
+                    match link_type {
+                        LinkTypes::AgentToProfile => validate_delete_link_agent_to_profile(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::NicknamePrefixes => validate_delete_link_nickname_prefixes(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::PrefixToAgents => validate_delete_link_prefix_to_agents(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                        LinkTypes::ProfileUpdates => validate_delete_link_profile_updates(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        ),
+                    }
+                }
                 OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
                 OpRecord::UpdatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
                 OpRecord::CreateCapClaim { .. } => Ok(ValidateCallbackResult::Valid),
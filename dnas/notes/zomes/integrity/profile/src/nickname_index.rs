@@ -0,0 +1,150 @@
+use hdi::prelude::*;
+
+pub const ALL_NICKNAMES_ROOT: &str = "all_nicknames";
+pub const NICKNAME_PREFIX_LENGTH: usize = 3;
+
+// Nicknames are bucketed by their first lowercase characters, so that a search only reads one bucket
+pub fn nickname_prefix(nickname: &str) -> String {
+    nickname
+        .trim()
+        .to_lowercase()
+        .chars()
+        .take(NICKNAME_PREFIX_LENGTH)
+        .collect()
+}
+
+pub fn nickname_prefix_path(prefix: &str) -> Path {
+    Path::from(vec![
+        Component::from(ALL_NICKNAMES_ROOT),
+        Component::from(prefix),
+    ])
+}
+
+// The string component that an hdk path link is tagged with, see `Path::make_tag`
+pub fn nickname_path_link_component(tag: &LinkTag) -> Option<String> {
+    let component =
+        Component::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0.clone()))).ok()?;
+    String::try_from(&component).ok()
+}
+
+// The nickname of the latest profile the author committed before the given action, if any
+fn latest_nickname(author: AgentPubKey, prev_action: ActionHash) -> ExternResult<Option<String>> {
+    let profile_entry_type: EntryType = crate::UnitEntryTypes::Profile.try_into()?;
+    let activity = must_get_agent_activity(author, ChainFilter::new(prev_action))?;
+    let Some(entry_hash) = activity
+        .into_iter()
+        .filter(|activity| activity.action.action().entry_type() == Some(&profile_entry_type))
+        .max_by_key(|activity| activity.action.action().action_seq())
+        .and_then(|activity| activity.action.action().entry_hash().cloned())
+    else {
+        return Ok(None);
+    };
+    let profile = crate::Profile::try_from(must_get_entry(entry_hash)?.content)?;
+    Ok(Some(profile.nickname))
+}
+
+// NicknamePrefixes links are the links `TypedPath::ensure` creates for a prefix path: one from the root
+// of all paths to the nicknames root, and one from the nicknames root to the prefix of the author's nickname
+pub fn validate_create_link_nickname_prefixes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(component) = nickname_path_link_component(&tag) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a NicknamePrefixes link must be a path component".to_string(),
+        ));
+    };
+    let root = Path::from(vec![Component::from(ALL_NICKNAMES_ROOT)]);
+    if base_address == hdi::hash_path::path::root_hash()? {
+        if component != ALL_NICKNAMES_ROOT
+            || target_address != AnyLinkableHash::from(root.path_entry_hash()?)
+        {
+            return Ok(ValidateCallbackResult::Invalid(
+                "The only NicknamePrefixes link from the root path must point to the nicknames root"
+                    .to_string(),
+            ));
+        }
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if base_address != AnyLinkableHash::from(root.path_entry_hash()?) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NicknamePrefixes link must hang off the nicknames root".to_string(),
+        ));
+    }
+    let nickname = latest_nickname(action.author, action.prev_action)?;
+    if nickname.map(|nickname| nickname_prefix(&nickname)) != Some(component.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Agents can only add the prefix of their own nickname".to_string(),
+        ));
+    }
+    if target_address != AnyLinkableHash::from(nickname_prefix_path(&component).path_entry_hash()?)
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A NicknamePrefixes link must point to the path of the prefix in its tag".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_nickname_prefixes(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "NicknamePrefixes links cannot be deleted".to_string(),
+    ))
+}
+
+// The link tag holds the lowercase nickname, so that searches can filter a bucket without fetching profiles
+// It must be the nickname of the author's latest profile, so that agents cannot index themselves under another's
+pub fn validate_create_link_prefix_to_agents(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_agent_pub_key().as_ref() != Some(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Agents can only index their own nickname".to_string(),
+        ));
+    }
+    let Ok(nickname) = String::from_utf8(tag.into_inner()) else {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The tag of a PrefixToAgents link must be the UTF-8 nickname".to_string(),
+        ));
+    };
+    let latest = latest_nickname(action.author, action.prev_action)?;
+    if latest.map(|latest| latest.trim().to_lowercase()) != Some(nickname.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "The nickname in a PrefixToAgents link must be the trimmed and lowercase nickname of the agent's profile"
+                .to_string(),
+        ));
+    }
+    let prefix_hash = nickname_prefix_path(&nickname_prefix(&nickname)).path_entry_hash()?;
+    if base_address != AnyLinkableHash::from(prefix_hash) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A PrefixToAgents link must hang off the path of its nickname prefix".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_prefix_to_agents(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can remove their nickname from the index".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -1,30 +1,146 @@
 use hdi::prelude::*;
+use std::collections::BTreeMap;
 
+pub const MIN_NICKNAME_LENGTH: usize = 3;
+pub const MAX_NICKNAME_LENGTH: usize = 32;
+pub const MAX_BIO_LENGTH: usize = 500;
+pub const MAX_TIMEZONE_LENGTH: usize = 64;
+pub const MAX_PROFILE_FIELDS: usize = 20;
+pub const MAX_FIELD_NAME_LENGTH: usize = 64;
+pub const MAX_FIELD_VALUE_BYTES: usize = 1024;
+
+// Fields added after the first release default when missing, so nickname-only entries keep deserializing
 #[derive(Clone, PartialEq)]
 #[hdk_entry_helper]
 pub struct Profile {
     pub nickname: String,
+    #[serde(default)]
+    pub avatar: Option<EntryHash>,
+    #[serde(default)]
+    pub bio: String,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+fn validate_nickname(nickname: &str) -> Option<String> {
+    let length = nickname.chars().count();
+    if !(MIN_NICKNAME_LENGTH..=MAX_NICKNAME_LENGTH).contains(&length) {
+        return Some(format!(
+            "Nickname must be between {MIN_NICKNAME_LENGTH} and {MAX_NICKNAME_LENGTH} characters"
+        ));
+    }
+    if !nickname
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.'))
+    {
+        return Some(
+            "Nickname can only contain letters, digits, spaces, '_', '-' and '.'".to_string(),
+        );
+    }
+    if nickname.trim() != nickname {
+        return Some("Nickname cannot start or end with a space".to_string());
+    }
+    None
+}
+
+fn validate_profile_fields(profile: &Profile) -> ExternResult<Option<String>> {
+    if let Some(error) = validate_nickname(&profile.nickname) {
+        return Ok(Some(error));
+    }
+    if profile.bio.chars().count() > MAX_BIO_LENGTH {
+        return Ok(Some(format!(
+            "Bio cannot be longer than {MAX_BIO_LENGTH} characters"
+        )));
+    }
+    if let Some(timezone) = &profile.timezone {
+        if timezone.is_empty()
+            || timezone.len() > MAX_TIMEZONE_LENGTH
+            || !timezone
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'))
+        {
+            return Ok(Some(format!(
+                "Timezone must be an IANA name like \"Europe/Paris\" of at most {MAX_TIMEZONE_LENGTH} characters"
+            )));
+        }
+    }
+    if profile.fields.len() > MAX_PROFILE_FIELDS {
+        return Ok(Some(format!(
+            "A profile cannot have more than {MAX_PROFILE_FIELDS} custom fields"
+        )));
+    }
+    for (name, value) in profile.fields.iter() {
+        if name.trim().is_empty() || name.chars().count() > MAX_FIELD_NAME_LENGTH {
+            return Ok(Some(format!(
+                "Custom field names must be between 1 and {MAX_FIELD_NAME_LENGTH} characters"
+            )));
+        }
+        if value.len() > MAX_FIELD_VALUE_BYTES {
+            return Ok(Some(format!(
+                "Custom field \"{name}\" cannot be larger than {MAX_FIELD_VALUE_BYTES} bytes"
+            )));
+        }
+    }
+    if let Some(avatar_hash) = &profile.avatar {
+        let entry = must_get_entry(avatar_hash.clone())?;
+        let Ok(avatar) = crate::Avatar::try_from(entry.as_content().clone()) else {
+            return Ok(Some(
+                "The avatar of a profile must be an Avatar entry".to_string(),
+            ));
+        };
+        if let Some(error) = crate::validate_avatar(&avatar) {
+            return Ok(Some(error));
+        }
+    }
+    Ok(None)
 }
 
 pub fn validate_create_profile(
-    _action: EntryCreationAction,
-    _profile: Profile,
+    action: EntryCreationAction,
+    profile: Profile,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
+    if let Some(error) = validate_profile_fields(&profile)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    // Updates of the profile are fine, but there can only be one Create of a profile per source chain
+    if let EntryCreationAction::Create(create) = action {
+        let profile_entry_type: EntryType = crate::UnitEntryTypes::Profile.try_into()?;
+        let activity = must_get_agent_activity(
+            create.author.clone(),
+            ChainFilter::new(create.prev_action.clone()),
+        )?;
+        let has_profile = activity.iter().any(|activity| {
+            matches!(activity.action.action(), Action::Create(previous) if previous.entry_type == profile_entry_type)
+        });
+        if has_profile {
+            return Ok(ValidateCallbackResult::Invalid(
+                "An agent can only create one profile".to_string(),
+            ));
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_update_profile(
-    _action: Update,
+    action: Update,
     profile: Profile,
-    _original_action: EntryCreationAction,
+    original_action: EntryCreationAction,
     original_profile: Profile,
 ) -> ExternResult<ValidateCallbackResult> {
+    if action.author != *original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a profile can update it".to_string(),
+        ));
+    }
     if profile == original_profile {
-        return Ok(ValidateCallbackResult::Invalid("No changes made to profile".into()));
+        return Ok(ValidateCallbackResult::Invalid(
+            "No changes made to profile".into(),
+        ));
     }
-    if profile.nickname.trim().is_empty() {
-        return Ok(ValidateCallbackResult::Invalid("Nickname cannot be empty".into()));
+    if let Some(error) = validate_profile_fields(&profile)? {
+        return Ok(ValidateCallbackResult::Invalid(error));
     }
     Ok(ValidateCallbackResult::Valid)
 }
@@ -38,3 +154,108 @@ pub fn validate_delete_profile(
         "Profiles cannot be deleted".to_string(),
     ))
 }
+
+pub fn validate_create_link_agent_to_profile(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address.into_agent_pub_key() != Some(action.author.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the agent themselves can link a profile off their own AgentPubKey".to_string(),
+        ));
+    }
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(action_hash)?;
+    let _profile: crate::Profile = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if *record.action().author() != action.author {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An AgentToProfile link can only point to a profile authored by that agent".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_agent_to_profile(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "AgentToProfile links cannot be deleted".to_string(),
+    ))
+}
+
+pub fn validate_create_link_profile_updates(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let original_record = must_get_valid_record(action_hash)?;
+    let _profile: crate::Profile = original_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let action_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let updated_record = must_get_valid_record(action_hash)?;
+    let _profile: crate::Profile = updated_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    if action.author != *original_record.action().author()
+        || updated_record.action().author() != original_record.action().author()
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Only the author of a profile can link updates to it".to_string(),
+        ));
+    }
+    if !matches!(updated_record.action(), Action::Update(_)) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "A ProfileUpdates link must point to an update".to_string(),
+        ));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_profile_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "ProfileUpdates links cannot be deleted".to_string(),
+    ))
+}
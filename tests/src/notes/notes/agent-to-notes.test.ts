@@ -0,0 +1,74 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote } from "./common.js";
+
+test("create Notes and get notes for agent", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice and Bob each create a Note
+    const aliceRecord: Record = await createNote(alice.cells[0]);
+    assert.ok(aliceRecord);
+    const bobRecord: Record = await createNote(bob.cells[0]);
+    assert.ok(bobRecord);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob gets Alice's notes
+    let collectionOutput: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_for_agent",
+      payload: alice.agentPubKey,
+    });
+    assert.equal(collectionOutput.length, 1);
+    assert.deepEqual(aliceRecord.signed_action.hashed.hash, collectionOutput[0].target);
+
+    // Bob gets his own notes
+    collectionOutput = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_notes",
+      payload: null,
+    });
+    assert.equal(collectionOutput.length, 1);
+    assert.deepEqual(bobRecord.signed_action.hashed.hash, collectionOutput[0].target);
+
+    // Alice deletes her Note
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "delete_note",
+      payload: aliceRecord.signed_action.hashed.hash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob gets Alice's notes again
+    collectionOutput = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_for_agent",
+      payload: alice.agentPubKey,
+    });
+    assert.equal(collectionOutput.length, 0);
+  });
+});
@@ -0,0 +1,111 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+// A PNG signature followed by enough bytes to span several chunks
+function samplePng(size: number): Uint8Array {
+  const bytes = new Uint8Array(size);
+  for (let i = 0; i < size; i++) bytes[i] = i % 251;
+  bytes.set([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]);
+  return bytes;
+}
+
+test("attach a file to a note, fetch it back, list and remove it", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const note: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0]));
+    const originalNoteHash = note.signed_action.hashed.hash;
+
+    // Alice attaches a screenshot spanning 3 chunks
+    const bytes = samplePng(2 * 1024 * 1024 + 10);
+    const manifestRecord: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "attach_file",
+      payload: { original_note_hash: originalNoteHash, name: "screenshot.png", mime_type: "image/png", bytes },
+    });
+    const manifestHash: ActionHash = manifestRecord.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob lists the attachments of the note and downloads the file
+    let links: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_attachments",
+      payload: originalNoteHash,
+    });
+    assert.equal(links.length, 1);
+    const attachment: any = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_attachment",
+      payload: links[0].target,
+    });
+    assert.equal(attachment.manifest.name, "screenshot.png");
+    assert.equal(attachment.manifest.chunks.length, 3);
+    assert.deepEqual(new Uint8Array(attachment.bytes), bytes);
+
+    // Files whose content does not match their declared type are rejected
+    await expect(alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "attach_file",
+      payload: { original_note_hash: originalNoteHash, name: "fake.pdf", mime_type: "application/pdf", bytes },
+    })).rejects.toThrow();
+
+    // Types outside of the allowed list are rejected
+    await expect(alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "attach_file",
+      payload: {
+        original_note_hash: originalNoteHash,
+        name: "page.html",
+        mime_type: "text/html",
+        bytes: new TextEncoder().encode("<html></html>"),
+      },
+    })).rejects.toThrow();
+
+    // Only the owner of the note can attach files to it
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "attach_file",
+      payload: { original_note_hash: originalNoteHash, name: "bob.png", mime_type: "image/png", bytes: samplePng(100) },
+    })).rejects.toThrow();
+
+    // Alice removes the attachment
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "remove_attachment",
+      payload: { original_note_hash: originalNoteHash, manifest_hash: manifestHash },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    links = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_attachments",
+      payload: originalNoteHash,
+    });
+    assert.equal(links.length, 0);
+  });
+});
@@ -0,0 +1,56 @@
+import { assert, test } from "vitest";
+
+import {
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createProfile, sampleProfile } from "../profile/common.js";
+import { createNote } from "./common.js";
+
+test("get Notes with their author's profile", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Profile and a Note, Bob only creates a Note
+    await createProfile(alice.cells[0], await sampleProfile(alice.cells[0], { nickname: "alice" }));
+    const aliceNote: Record = await createNote(alice.cells[0]);
+    const bobNote: Record = await createNote(bob.cells[0]);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob gets Alice's Note along with her nickname
+    const noteWithAuthor = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_with_author",
+      payload: aliceNote.signed_action.hashed.hash,
+    });
+    assert.deepEqual(noteWithAuthor.note.author, alice.agentPubKey);
+    assert.equal(noteWithAuthor.author_profile.nickname, "alice");
+
+    // Bob gets both Notes at once
+    const notesWithAuthors = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_with_authors",
+      payload: [aliceNote.signed_action.hashed.hash, bobNote.signed_action.hashed.hash],
+    });
+    assert.equal(notesWithAuthors.length, 2);
+    assert.equal(notesWithAuthors[0].author_profile.nickname, "alice");
+    assert.equal(notesWithAuthors[1].author_profile, null);
+  });
+});
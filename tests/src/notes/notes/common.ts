@@ -16,7 +16,10 @@ export async function sampleNote(cell: CallableCell, partialNote = {}) {
     ...{
       title: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
       content: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
-      created_at: 1674053334548000,
+      created_at: Date.now() * 1000,
+      tags: [],
+      editors: [],
+      format: "Plain",
     },
     ...partialNote,
   };
@@ -29,3 +32,8 @@ export async function createNote(cell: CallableCell, note = undefined): Promise<
     payload: note || await sampleNote(cell),
   });
 }
+
+export interface LatestNote {
+  record: Record;
+  diverged: boolean;
+}
@@ -0,0 +1,143 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AgentPubKey,
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("only the owner and editors of a Note can update it", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Note
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob is not an editor yet, so he can't update the Note
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: originalHash,
+        updated_note: { ...sample, content: "Bob was here" },
+      },
+    })).rejects.toThrow();
+
+    // Alice adds Bob as an editor
+    const withEditor: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "add_editor",
+      payload: { original_note_hash: originalHash, editor: bob.agentPubKey },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const editors: AgentPubKey[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_editors",
+      payload: originalHash,
+    });
+    assert.deepEqual(editors, [bob.agentPubKey]);
+
+    // Bob can now update the Note, but can't change its editors
+    const bobUpdate: Record = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: withEditor.signed_action.hashed.hash,
+        updated_note: { ...sample, content: "Bob was here", editors: [bob.agentPubKey] },
+      },
+    });
+    assert.ok(bobUpdate);
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: bobUpdate.signed_action.hashed.hash,
+        updated_note: { ...sample, editors: [] },
+      },
+    })).rejects.toThrow();
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob's edit is indexed for search right away
+    const hits: any[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "search_notes",
+      payload: "bob",
+    });
+    assert.equal(hits.length, 1);
+    assert.deepEqual(hits[0].note.original_note_hash, originalHash);
+
+    // Alice removes Bob, who can't update the Note anymore
+    const withoutEditor: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "remove_editor",
+      payload: { original_note_hash: originalHash, editor: bob.agentPubKey },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: withoutEditor.signed_action.hashed.hash,
+        updated_note: { ...sample, content: "Bob was here again" },
+      },
+    })).rejects.toThrow();
+
+    // Nor can Bob fork an older revision that still listed him
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: withEditor.signed_action.hashed.hash,
+        updated_note: { ...sample, content: "Bob forked", editors: [bob.agentPubKey] },
+      },
+    })).rejects.toThrow();
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const heads: Record[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_heads",
+      payload: originalHash,
+    });
+    assert.deepEqual(heads.map(head => head.signed_action.hashed.hash), [withoutEditor.signed_action.hashed.hash]);
+    const latest = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_latest_note",
+      payload: originalHash,
+    });
+    assert.deepEqual(latest.record.signed_action.hashed.hash, withoutEditor.signed_action.hashed.hash);
+    assert.isFalse(latest.diverged);
+  });
+});
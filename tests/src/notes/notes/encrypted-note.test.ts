@@ -0,0 +1,83 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+
+import { sampleNote } from "./common.js";
+
+test("create, decrypt and update an EncryptedNote", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates an EncryptedNote
+    const sample = await sampleNote(alice.cells[0], { title: "Secret", content: "Top secret content" });
+    const record: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "create_encrypted_note",
+      payload: sample,
+    });
+    assert.ok(record);
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+
+    // Alice decrypts it
+    let decrypted = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    });
+    assert.deepEqual(decrypted, sample);
+
+    // Alice updates it
+    const updatedNote = { ...sample, content: "Even more secret content" };
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_encrypted_note",
+      payload: {
+        original_encrypted_note_hash: originalHash,
+        previous_encrypted_note_hash: originalHash,
+        updated_note: updatedNote,
+      },
+    });
+    decrypted = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    });
+    assert.deepEqual(decrypted, updatedNote);
+
+    const myEncryptedNotes: Link[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_encrypted_notes",
+      payload: null,
+    });
+    assert.equal(myEncryptedNotes.length, 1);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can fetch the ciphertext but cannot decrypt it
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    })).rejects.toThrow();
+  });
+});
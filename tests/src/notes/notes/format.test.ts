@@ -0,0 +1,95 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("Notes render a plain text excerpt of their format and reject raw HTML", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    // Notes without a format are plain text
+    const legacy: any = await sampleNote(alice.cells[0]);
+    delete legacy.format;
+    const legacyRecord: Record = await createNote(alice.cells[0], legacy);
+    assert.equal((decode((legacyRecord.entry as any).Present.entry) as any).format, "Plain");
+
+    // Markdown is stripped of its markup
+    const markdown: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "Markdown",
+      content: "# Plans\n\nRead the **[docs](https://developer.holochain.org)** and <https://holochain.org>",
+    }));
+    let excerpt: string = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_excerpt",
+      payload: markdown.signed_action.hashed.hash,
+    });
+    assert.equal(excerpt, "Plans Read the docs and <https://holochain.org>");
+
+    // Rich text keeps the text of its nodes
+    const richText: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "RichText",
+      content: JSON.stringify({
+        type: "doc",
+        content: [
+          { type: "paragraph", content: [{ type: "text", text: "Hello " }, { type: "text", text: "world" }] },
+          { type: "paragraph", content: [{ type: "text", text: "Second paragraph" }] },
+        ],
+      }),
+    }));
+    excerpt = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_excerpt",
+      payload: richText.signed_action.hashed.hash,
+    });
+    assert.equal(excerpt, "Hello world Second paragraph");
+
+    // Raw HTML and script URLs are rejected in rich formats
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "Markdown",
+      content: "Hi <script>alert(1)</script>",
+    }))).rejects.toThrow();
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "Markdown",
+      content: "[click](javascript:alert(1))",
+    }))).rejects.toThrow();
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "RichText",
+      content: JSON.stringify({ type: "doc", content: [{ type: "text", text: "<img src=x onerror=alert(1)>" }] }),
+    }))).rejects.toThrow();
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "RichText",
+      content: "not json",
+    }))).rejects.toThrow();
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "RichText",
+      content: JSON.stringify({ type: "text", text: "click", marks: [{ type: "link", attrs: { href: "javascript:alert(1)" } }] }),
+    }))).rejects.toThrow();
+
+    // Comparisons and the names of URL schemes in prose are not markup
+    for (const content of ["a <b", "x < y", "Notes on javascript: the good parts", "See <https://holochain.org> for x < y"]) {
+      const record: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], { format: "Markdown", content }));
+      assert.ok(record);
+    }
+    const prose: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      format: "RichText",
+      content: JSON.stringify({ type: "doc", content: [{ type: "text", text: "x < y, and javascript: is a language" }] }),
+    }));
+    assert.ok(prose);
+  });
+});
@@ -0,0 +1,106 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createNote, LatestNote, sampleNote } from "./common.js";
+
+test("detect and merge concurrent updates of a Note", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Note and makes Bob an editor
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+    const withEditor: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "add_editor",
+      payload: { original_note_hash: originalHash, editor: bob.agentPubKey },
+    });
+    const base = decode((withEditor.entry as any).Present.entry) as any;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice and Bob both update the same revision
+    for (const [player, content] of [[alice, "Alice's edit"], [bob, "Bob's edit"]] as const) {
+      await player.cells[0].callZome({
+        zome_name: "notes",
+        fn_name: "update_note",
+        payload: {
+          original_note_hash: originalHash,
+          previous_note_hash: withEditor.signed_action.hashed.hash,
+          updated_note: { ...base, content },
+        },
+      });
+    }
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Both edits are heads, and the latest note is flagged as diverged
+    const heads: Record[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_heads",
+      payload: originalHash,
+    });
+    assert.equal(heads.length, 2);
+    let latest: LatestNote = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_latest_note",
+      payload: originalHash,
+    });
+    assert.ok(latest.diverged);
+
+    // Listings resolve the same revision and flag the divergence too
+    const page: any = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_notes_page",
+      payload: { limit: 10, cursor: null, sort: "NewestFirst" },
+    });
+    assert.equal(page.notes.length, 1);
+    assert.ok(page.notes[0].diverged);
+    assert.deepEqual(page.notes[0].record.signed_action.hashed.hash, latest.record.signed_action.hashed.hash);
+
+    // Alice merges both edits
+    const mergedNote = { ...base, content: "Alice's edit and Bob's edit" };
+    const merge: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "merge_note",
+      payload: {
+        original_note_hash: originalHash,
+        heads: heads.map(head => head.signed_action.hashed.hash),
+        merged_note: mergedNote,
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    latest = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_latest_note",
+      payload: originalHash,
+    });
+    assert.notOk(latest.diverged);
+    assert.deepEqual(latest.record.signed_action.hashed.hash, merge.signed_action.hashed.hash);
+    assert.deepEqual(decode((latest.record.entry as any).Present.entry) as any, mergedNote);
+  });
+});
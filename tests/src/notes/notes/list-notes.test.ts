@@ -13,7 +13,7 @@ import {
 import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
 import { decode } from "@msgpack/msgpack";
 
-import { createNote } from "./common.js";
+import { createNote, sampleNote } from "./common.js";
 
 test("create a Note and get list notes", async () => {
   await runScenario(async scenario => {
@@ -73,3 +73,62 @@ test("create a Note and get list notes", async () => {
     assert.equal(collectionOutput.length, 0);
   });
 });
+
+test("create and update Notes and get a hydrated list page", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Notes and updates the first one
+    const sample = await sampleNote(alice.cells[0]);
+    const firstRecord: Record = await createNote(alice.cells[0], sample);
+    const secondRecord: Record = await createNote(alice.cells[0]);
+    const updatedNote = { ...sample, title: "Updated title" };
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: firstRecord.signed_action.hashed.hash,
+        previous_note_hash: firstRecord.signed_action.hashed.hash,
+        updated_note: updatedNote,
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob gets the first page, newest first
+    let page: any = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_notes_page",
+      payload: { limit: 1, cursor: null, sort: "NewestFirst" },
+    });
+    assert.equal(page.notes.length, 1);
+    assert.deepEqual(page.notes[0].original_note_hash, secondRecord.signed_action.hashed.hash);
+    assert.ok(page.next_cursor);
+
+    // Bob gets the second page, which holds the latest revision of the first note
+    page = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_notes_page",
+      payload: { limit: 1, cursor: page.next_cursor, sort: "NewestFirst" },
+    });
+    assert.equal(page.notes.length, 1);
+    assert.deepEqual(page.notes[0].original_note_hash, firstRecord.signed_action.hashed.hash);
+    assert.equal(page.notes[0].title, "Updated title");
+    assert.deepEqual(page.notes[0].author, alice.agentPubKey);
+    assert.equal(page.next_cursor, null);
+  });
+});
@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import {
   ActionHash,
@@ -16,7 +16,7 @@ import {
 import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
 import { decode } from "@msgpack/msgpack";
 
-import { createNote, sampleNote } from "./common.js";
+import { createNote, LatestNote, sampleNote } from "./common.js";
 
 test("create Note", async () => {
   await runScenario(async scenario => {
@@ -97,14 +97,16 @@ test("create and update Note", async () => {
     // conductor of the scenario.
     await scenario.shareAllAgents();
 
+    const sample = await sampleNote(alice.cells[0]);
+
     // Alice creates a Note
-    const record: Record = await createNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
     assert.ok(record);
 
     const originalActionHash = record.signed_action.hashed.hash;
 
     // Alice updates the Note
-    let contentUpdate: any = await sampleNote(alice.cells[0]);
+    let contentUpdate: any = await sampleNote(alice.cells[0], { created_at: sample.created_at });
     let updateInput = {
       original_note_hash: originalActionHash,
       previous_note_hash: originalActionHash,
@@ -122,15 +124,15 @@ test("create and update Note", async () => {
     await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
 
     // Bob gets the updated Note
-    const readUpdatedOutput0: Record = await bob.cells[0].callZome({
+    const readUpdatedOutput0: LatestNote = await bob.cells[0].callZome({
       zome_name: "notes",
       fn_name: "get_latest_note",
       payload: updatedRecord.signed_action.hashed.hash,
     });
-    assert.deepEqual(contentUpdate, decode((readUpdatedOutput0.entry as any).Present.entry) as any);
+    assert.deepEqual(contentUpdate, decode((readUpdatedOutput0.record.entry as any).Present.entry) as any);
 
     // Alice updates the Note again
-    contentUpdate = await sampleNote(alice.cells[0]);
+    contentUpdate = await sampleNote(alice.cells[0], { created_at: sample.created_at });
     updateInput = {
       original_note_hash: originalActionHash,
       previous_note_hash: updatedRecord.signed_action.hashed.hash,
@@ -148,12 +150,12 @@ test("create and update Note", async () => {
     await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
 
     // Bob gets the updated Note
-    const readUpdatedOutput1: Record = await bob.cells[0].callZome({
+    const readUpdatedOutput1: LatestNote = await bob.cells[0].callZome({
       zome_name: "notes",
       fn_name: "get_latest_note",
       payload: updatedRecord.signed_action.hashed.hash,
     });
-    assert.deepEqual(contentUpdate, decode((readUpdatedOutput1.entry as any).Present.entry) as any);
+    assert.deepEqual(contentUpdate, decode((readUpdatedOutput1.record.entry as any).Present.entry) as any);
 
     // Bob gets all the revisions for Note
     const revisions: Record[] = await bob.cells[0].callZome({
@@ -190,15 +192,29 @@ test("create and delete Note", async () => {
     const record: Record = await createNote(alice.cells[0], sample);
     assert.ok(record);
 
+    // Alice updates the Note
+    const updatedRecord: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: record.signed_action.hashed.hash,
+        previous_note_hash: record.signed_action.hashed.hash,
+        updated_note: { ...sample, title: "Updated title" },
+      },
+    });
+
     await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
 
-    // Alice deletes the Note
-    const deleteActionHash = await alice.cells[0].callZome({
+    // Alice deletes the Note, which deletes its revision and index links too
+    const report = await alice.cells[0].callZome({
       zome_name: "notes",
       fn_name: "delete_note",
       payload: record.signed_action.hashed.hash,
     });
-    assert.ok(deleteActionHash);
+    assert.ok(report.delete_action_hash);
+    assert.deepEqual(report.deleted_revisions, [updatedRecord.signed_action.hashed.hash]);
+    // The time index and AgentToNotes links, and the links of the 10 search terms of the latest revision
+    assert.equal(report.removed_links.length, 12);
 
     // Wait for the entry deletion to be propagated to the other node.
     await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
@@ -218,5 +234,102 @@ test("create and delete Note", async () => {
       payload: record.signed_action.hashed.hash,
     });
     assert.equal(deletesForNote.length, 1);
+
+    // The revision was deleted as well
+    const deletesForRevision: SignedActionHashed[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_all_deletes_for_note",
+      payload: updatedRecord.signed_action.hashed.hash,
+    });
+    assert.equal(deletesForRevision.length, 1);
+  });
+});
+
+test("only the author can update or delete a Note", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Note
+    const record: Record = await createNote(alice.cells[0]);
+    assert.ok(record);
+
+    const originalActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob tries to update Alice's Note
+    const updateInput = {
+      original_note_hash: originalActionHash,
+      previous_note_hash: originalActionHash,
+      updated_note: await sampleNote(bob.cells[0], { title: "Bob was here" }),
+    };
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: updateInput,
+    })).rejects.toThrow();
+
+    // Bob tries to delete Alice's Note
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "delete_note",
+      payload: originalActionHash,
+    })).rejects.toThrow();
+  });
+});
+
+test("create a Note with invalid content", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice tries to create a Note with a blank title
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], { title: "   " })))
+      .rejects.toThrow();
+
+    // Alice tries to create a Note dated a year into the future
+    const nextYear = Date.now() * 1000 + 365 * 24 * 60 * 60 * 1000 * 1000;
+    await expect(createNote(alice.cells[0], await sampleNote(alice.cells[0], { created_at: nextYear })))
+      .rejects.toThrow();
+
+    // Alice creates a valid Note and tries to change its created_at
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
+    await expect(alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: record.signed_action.hashed.hash,
+        previous_note_hash: record.signed_action.hashed.hash,
+        updated_note: { ...sample, created_at: sample.created_at - 1000 },
+      },
+    })).rejects.toThrow();
   });
 });
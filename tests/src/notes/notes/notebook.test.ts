@@ -0,0 +1,135 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createNote } from "./common.js";
+
+test("nest Notebooks, move Notes between them and prevent cycles", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a "Work" notebook with a "Projects" notebook inside
+    const work: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "create_notebook",
+      payload: { name: "Work", parent: null },
+    });
+    const workHash: ActionHash = work.signed_action.hashed.hash;
+    const projects: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "create_notebook",
+      payload: { name: "Projects", parent: workHash },
+    });
+    const projectsHash: ActionHash = projects.signed_action.hashed.hash;
+
+    // She files a Note in "Work", then moves it to "Projects"
+    const note: Record = await createNote(alice.cells[0]);
+    const noteHash: ActionHash = note.signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "move_note",
+      payload: { original_note_hash: noteHash, from_notebook: null, to_notebook: workHash },
+    });
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "move_note",
+      payload: { original_note_hash: noteHash, from_notebook: workHash, to_notebook: projectsHash },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    let contents: { notebooks: Link[]; notes: Link[] } = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notebook_contents",
+      payload: workHash,
+    });
+    assert.equal(contents.notebooks.length, 1);
+    assert.deepEqual(contents.notebooks[0].target, projectsHash);
+    assert.equal(contents.notes.length, 0);
+    contents = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notebook_contents",
+      payload: projectsHash,
+    });
+    assert.deepEqual(contents.notes.map(link => link.target), [noteHash]);
+
+    // "Work" cannot be moved inside "Projects", which is inside it
+    await expect(alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "move_notebook",
+      payload: { original_notebook_hash: workHash, previous_notebook_hash: workHash, parent: projectsHash },
+    })).rejects.toThrow();
+
+    // Alice renames "Work" and moves "Projects" to the top level
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "rename_notebook",
+      payload: { original_notebook_hash: workHash, previous_notebook_hash: workHash, name: "Office" },
+    });
+    const moved: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "move_notebook",
+      payload: { original_notebook_hash: projectsHash, previous_notebook_hash: projectsHash, parent: null },
+    });
+
+    // A rename changes the revision it builds on, keeping the move
+    const renamed: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "rename_notebook",
+      payload: {
+        original_notebook_hash: projectsHash,
+        previous_notebook_hash: moved.signed_action.hashed.hash,
+        name: "Side projects",
+      },
+    });
+    assert.deepEqual(decode((renamed.entry as any).Present.entry), { name: "Side projects", parent: null });
+    const notebooks: Link[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_notebooks",
+      payload: null,
+    });
+    assert.equal(notebooks.length, 2);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const latest: Record = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_latest_notebook",
+      payload: workHash,
+    });
+    assert.ok(latest);
+
+    // Bob cannot nest his notebooks in Alice's, nor file notes in them
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "create_notebook",
+      payload: { name: "Intruder", parent: workHash },
+    })).rejects.toThrow();
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "move_note",
+      payload: { original_note_hash: noteHash, from_notebook: null, to_notebook: workHash },
+    })).rejects.toThrow();
+  });
+});
@@ -0,0 +1,64 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote } from "./common.js";
+
+test("create Notes and page through them by time", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const start = Date.now() * 1000;
+
+    // Alice creates 3 Notes
+    const records: Record[] = [];
+    for (let i = 0; i < 3; i++) {
+      records.push(await createNote(alice.cells[0]));
+    }
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const end = Date.now() * 1000 + 1000;
+
+    // Bob gets the first page of notes, newest first
+    let page: { links: Link[]; next_cursor: { created_at: number; note_hash: ActionHash } | null } = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_in_range",
+      payload: { start, end, limit: 2, cursor: null },
+    });
+    assert.equal(page.links.length, 2);
+    assert.deepEqual(records[2].signed_action.hashed.hash, page.links[0].target);
+    assert.deepEqual(records[1].signed_action.hashed.hash, page.links[1].target);
+    assert.ok(page.next_cursor);
+
+    // Bob gets the second page
+    page = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_in_range",
+      payload: { start, end, limit: 2, cursor: page.next_cursor },
+    });
+    assert.equal(page.links.length, 1);
+    assert.deepEqual(records[0].signed_action.hashed.hash, page.links[0].target);
+    assert.equal(page.next_cursor, null);
+  });
+});
@@ -0,0 +1,244 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  encodeHashToBase64,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("[[...]] references link notes to each other and back", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const roadmap: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Roadmap",
+    }));
+    const roadmapHash: ActionHash = roadmap.signed_action.hashed.hash;
+    const retro: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Retro",
+    }));
+    const retroHash: ActionHash = retro.signed_action.hashed.hash;
+
+    // Alice references one note by title and the other by hash, plus one that does not exist
+    const meeting: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Meeting",
+      content: `See [[roadmap]] and [[${encodeHashToBase64(retroHash)}]], but not [[Nowhere]]`,
+    }));
+    const meetingHash: ActionHash = meeting.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    let outgoing: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_outgoing_links",
+      payload: meetingHash,
+    });
+    assert.sameDeepMembers(outgoing.map(link => link.target), [roadmapHash, retroHash]);
+    let backlinks: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_backlinks",
+      payload: roadmapHash,
+    });
+    assert.deepEqual(backlinks.map(link => link.target), [meetingHash]);
+
+    // A revision that drops a reference removes its links
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: meetingHash,
+        previous_note_hash: meetingHash,
+        updated_note: await sampleNote(alice.cells[0], { title: "Meeting", content: "Only [[Retro]] now" }),
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    outgoing = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_outgoing_links",
+      payload: meetingHash,
+    });
+    assert.deepEqual(outgoing.map(link => link.target), [retroHash]);
+    backlinks = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_backlinks",
+      payload: roadmapHash,
+    });
+    assert.equal(backlinks.length, 0);
+    backlinks = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_backlinks",
+      payload: retroHash,
+    });
+    assert.deepEqual(backlinks.map(link => link.target), [meetingHash]);
+  });
+});
+
+test("[[...]] references resolve titles too short to be search terms", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const a: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], { title: "A" }));
+    const aHash: ActionHash = a.signed_action.hashed.hash;
+    const c1: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], { title: "C 1" }));
+    const c1Hash: ActionHash = c1.signed_action.hashed.hash;
+
+    // Titles match regardless of case and of extra whitespace
+    const meeting: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Meeting",
+      content: "See [[a]] and [[ c   1 ]]",
+    }));
+    const meetingHash: ActionHash = meeting.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const outgoing: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_outgoing_links",
+      payload: meetingHash,
+    });
+    assert.sameDeepMembers(outgoing.map(link => link.target), [aHash, c1Hash]);
+
+    // A note renamed away from a title no longer resolves from it
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: aHash,
+        previous_note_hash: aHash,
+        updated_note: await sampleNote(alice.cells[0], { title: "B" }),
+      },
+    });
+    const agenda: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Agenda",
+      content: "See [[A]] and [[b]]",
+    }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const agendaLinks: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_outgoing_links",
+      payload: agenda.signed_action.hashed.hash,
+    });
+    assert.deepEqual(agendaLinks.map(link => link.target), [aHash]);
+  });
+});
+
+test("[[...]] references written by concurrent updates keep their backlink once merged", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const roadmap: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Roadmap",
+    }));
+    const roadmapHash: ActionHash = roadmap.signed_action.hashed.hash;
+    const meeting: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Meeting",
+    }));
+    const meetingHash: ActionHash = meeting.signed_action.hashed.hash;
+    const withEditor: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "add_editor",
+      payload: { original_note_hash: meetingHash, editor: bob.agentPubKey },
+    });
+    const base = decode((withEditor.entry as any).Present.entry) as any;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Alice and Bob both add the same reference, each linking it
+    for (const [player, content] of [[alice, "See [[Roadmap]]"], [bob, "Read [[Roadmap]]"]] as const) {
+      await player.cells[0].callZome({
+        zome_name: "notes",
+        fn_name: "update_note",
+        payload: {
+          original_note_hash: meetingHash,
+          previous_note_hash: withEditor.signed_action.hashed.hash,
+          updated_note: { ...base, content },
+        },
+      });
+    }
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    const heads: Record[] = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_note_heads",
+      payload: meetingHash,
+    });
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "merge_note",
+      payload: {
+        original_note_hash: meetingHash,
+        heads: heads.map(head => head.signed_action.hashed.hash),
+        merged_note: { ...base, content: "See and read [[Roadmap]]" },
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // The merge drops the duplicate reference, but the note still links back
+    const outgoing: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_outgoing_links",
+      payload: meetingHash,
+    });
+    assert.deepEqual(outgoing.map(link => link.target), [roadmapHash]);
+    const backlinks: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_backlinks",
+      payload: roadmapHash,
+    });
+    assert.deepEqual(backlinks.map(link => link.target), [meetingHash]);
+  });
+});
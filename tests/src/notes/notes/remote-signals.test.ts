@@ -0,0 +1,62 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { dhtSync, pause, runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("editors of a Note are signaled when it is updated", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Bob makes a zome call so that his cell is initialized and grants access to its remote signal handler
+    await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_notes",
+      payload: null,
+    });
+
+    // Bob listens to the note signals of his conductor
+    const signals: any[] = [];
+    bob.appWs.on("signal", (signal: any) => {
+      const payload = signal.value?.payload ?? signal.App?.payload;
+      if (payload?.type === "EntryUpdated") signals.push(payload);
+    });
+
+    // Alice creates a Note and makes Bob an editor
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "add_editor",
+      payload: { original_note_hash: originalHash, editor: bob.agentPubKey },
+    });
+
+    await pause(1000);
+
+    // Bob heard about the update that made him an editor
+    assert.equal(signals.length, 1);
+    assert.deepEqual(signals[0].action.hashed.content.author, alice.agentPubKey);
+    assert.deepEqual(signals[0].app_entry.editors, [bob.agentPubKey]);
+  });
+});
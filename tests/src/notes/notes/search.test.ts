@@ -0,0 +1,133 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("search Notes by the terms of their title and content", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two Notes
+    const sample = await sampleNote(alice.cells[0], {
+      title: "Garden plans",
+      content: "Plant tomatoes in spring, and water the Tomatoes daily.",
+    });
+    const gardenRecord: Record = await createNote(alice.cells[0], sample);
+    const gardenHash: ActionHash = gardenRecord.signed_action.hashed.hash;
+    await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "Shopping list",
+      content: "Tomatoes, bread and cheese.",
+    }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob searches regardless of case, the note mentioning tomatoes the most ranking first
+    let hits: any[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "search_notes",
+      payload: "TOMATOES",
+    });
+    assert.equal(hits.length, 2);
+    assert.deepEqual(hits[0].note.original_note_hash, gardenHash);
+    assert.equal(hits[0].score, 2);
+    assert.equal(hits[0].snippet, "Plant tomatoes in spring, and water the Tomatoes daily.");
+
+    // Notes matching more terms rank first
+    hits = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "search_notes",
+      payload: "tomatoes bread",
+    });
+    assert.equal(hits.length, 2);
+    assert.equal(hits[0].note.title, "Shopping list");
+    assert.equal(hits[0].matched_terms, 2);
+
+    // Alice rewrites the garden Note, which moves it to other terms
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: gardenHash,
+        previous_note_hash: gardenHash,
+        updated_note: { ...sample, content: "Plant potatoes in spring." },
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    hits = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "search_notes",
+      payload: "tomatoes",
+    });
+    assert.equal(hits.length, 1);
+    assert.equal(hits[0].note.title, "Shopping list");
+
+    hits = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "search_notes",
+      payload: "potatoes",
+    });
+    assert.equal(hits.length, 1);
+    assert.deepEqual(hits[0].note.original_note_hash, gardenHash);
+  });
+});
+
+test("search Notes whose terms change length when lowercased", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // "İ" lowercases to "i" followed by a combining dot
+    const record: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], {
+      title: "İstanbul trip",
+      content: "Visit the GROẞE Basar and the Ὀδυσσεύς exhibit.",
+    }));
+    const tripHash: ActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    for (const query of ["istanbul", "İSTANBUL", "große", "ὀδυσσεύς"]) {
+      const hits: any[] = await bob.cells[0].callZome({
+        zome_name: "notes",
+        fn_name: "search_notes",
+        payload: query,
+      });
+      assert.equal(hits.length, 1, query);
+      assert.deepEqual(hits[0].note.original_note_hash, tripHash);
+    }
+  });
+});
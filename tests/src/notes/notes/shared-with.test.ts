@@ -0,0 +1,123 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+
+import { sampleNote } from "./common.js";
+
+test("share an EncryptedNote and revoke the share", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 3 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob, carol] = await scenario.addPlayersWithApps([appSource, appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Bob and Carol publish their encryption keys
+    for (const player of [bob, carol]) {
+      await player.cells[0].callZome({
+        zome_name: "notes",
+        fn_name: "publish_encryption_key",
+        payload: null,
+      });
+    }
+
+    // Alice creates an EncryptedNote
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "create_encrypted_note",
+      payload: sample,
+    });
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+
+    await dhtSync([alice, bob, carol], alice.cells[0].cell_id[0]);
+
+    // Alice shares it with Bob and Carol
+    const shared: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "share_note",
+      payload: {
+        original_encrypted_note_hash: originalHash,
+        recipients: [bob.agentPubKey, carol.agentPubKey],
+      },
+    });
+
+    await dhtSync([alice, bob, carol], alice.cells[0].cell_id[0]);
+
+    // Bob sees the note shared with him and can decrypt it
+    const sharedWithBob: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_shared_with_me",
+      payload: null,
+    });
+    assert.equal(sharedWithBob.length, 1);
+    assert.deepEqual(sharedWithBob[0].target, originalHash);
+    // The share points at the revision that holds Bob's key envelope
+    assert.deepEqual(new Uint8Array(sharedWithBob[0].tag), new Uint8Array(shared.signed_action.hashed.hash));
+    const decrypted = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    });
+    assert.deepEqual(decrypted, sample);
+
+    // Alice revokes Bob's share and updates the note
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "revoke_share",
+      payload: { original_encrypted_note_hash: originalHash, recipient: bob.agentPubKey },
+    });
+    const latest: Record = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_latest_encrypted_note",
+      payload: originalHash,
+    });
+    const updatedNote = { ...sample, content: "Not for Bob anymore" };
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_encrypted_note",
+      payload: {
+        original_encrypted_note_hash: originalHash,
+        previous_encrypted_note_hash: latest.signed_action.hashed.hash,
+        updated_note: updatedNote,
+      },
+    });
+
+    await dhtSync([alice, bob, carol], alice.cells[0].cell_id[0]);
+
+    // Bob no longer sees the note nor can decrypt the latest revision, Carol still can
+    const sharedWithBobAfterRevoke: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_shared_with_me",
+      payload: null,
+    });
+    assert.equal(sharedWithBobAfterRevoke.length, 0);
+    await expect(bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    })).rejects.toThrow();
+    const decryptedByCarol = await carol.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "decrypt_encrypted_note",
+      payload: originalHash,
+    });
+    assert.deepEqual(decryptedByCarol, updatedNote);
+  });
+});
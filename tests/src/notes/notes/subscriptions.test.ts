@@ -0,0 +1,104 @@
+import { assert, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, pause, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("subscribe to a Note and an author, then unsubscribe", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const record: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0]));
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob follows the note and its author, subscribing twice is a no-op
+    await bob.cells[0].callZome({ zome_name: "notes", fn_name: "subscribe_to_note", payload: originalHash });
+    await bob.cells[0].callZome({ zome_name: "notes", fn_name: "subscribe_to_note", payload: originalHash });
+    await bob.cells[0].callZome({ zome_name: "notes", fn_name: "subscribe_to_author", payload: alice.agentPubKey });
+
+    let subscriptions: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_subscriptions",
+      payload: null,
+    });
+    assert.equal(subscriptions.length, 2);
+
+    await bob.cells[0].callZome({ zome_name: "notes", fn_name: "unsubscribe_from_note", payload: originalHash });
+    subscriptions = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_my_subscriptions",
+      payload: null,
+    });
+    assert.equal(subscriptions.length, 1);
+    assert.deepEqual(subscriptions[0].target, alice.agentPubKey);
+  });
+});
+
+test("subscribers of an author are signaled when their notes are updated", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    await bob.cells[0].callZome({ zome_name: "notes", fn_name: "subscribe_to_author", payload: alice.agentPubKey });
+
+    const signals: any[] = [];
+    bob.appWs.on("signal", (signal: any) => {
+      const payload = signal.value?.payload ?? signal.App?.payload;
+      if (payload?.type === "EntryUpdated" || payload?.type === "EntryDeleted") signals.push(payload);
+    });
+
+    const sample = await sampleNote(alice.cells[0]);
+    const record: Record = await createNote(alice.cells[0], sample);
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: originalHash,
+        updated_note: { ...sample, title: "Updated title" },
+      },
+    });
+    await pause(1000);
+
+    assert.equal(signals.length, 1);
+    assert.equal(signals[0].type, "EntryUpdated");
+    assert.equal(signals[0].app_entry.title, "Updated title");
+  });
+});
@@ -0,0 +1,90 @@
+import { assert, test } from "vitest";
+
+import {
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("tag Notes, retag them and filter by tag", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates two tagged Notes
+    const sample = await sampleNote(alice.cells[0], { tags: ["Work", "ideas"] });
+    const workRecord: Record = await createNote(alice.cells[0], sample);
+    await createNote(alice.cells[0], await sampleNote(alice.cells[0], { tags: ["ideas"] }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob filters the notes by tag, ignoring case
+    let links: Link[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_by_tag",
+      payload: "work",
+    });
+    assert.equal(links.length, 1);
+    assert.deepEqual(links[0].target, workRecord.signed_action.hashed.hash);
+
+    // Bob lists all tags with their counts
+    let tags: { tag: string; count: number }[] = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_all_tags",
+      payload: null,
+    });
+    assert.deepEqual(tags, [{ tag: "ideas", count: 2 }, { tag: "work", count: 1 }]);
+
+    // Alice moves the first Note from "work" to "personal"
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: workRecord.signed_action.hashed.hash,
+        previous_note_hash: workRecord.signed_action.hashed.hash,
+        updated_note: { ...sample, tags: ["ideas", "personal"] },
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    tags = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "list_all_tags",
+      payload: null,
+    });
+    assert.deepEqual(tags, [{ tag: "ideas", count: 2 }, { tag: "personal", count: 1 }]);
+
+    // Alice deletes the first Note
+    await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "delete_note",
+      payload: workRecord.signed_action.hashed.hash,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    links = await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_by_tag",
+      payload: "personal",
+    });
+    assert.equal(links.length, 0);
+  });
+});
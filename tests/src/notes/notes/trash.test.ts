@@ -0,0 +1,126 @@
+import { assert, expect, test } from "vitest";
+
+import {
+  ActionHash,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { dhtSync, runScenario } from "@holochain/tryorama";
+
+import { createNote, sampleNote } from "./common.js";
+
+test("trash a Note, restore it, then purge it", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    const record: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0], { tags: ["work"] }));
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+
+    // Alice moves the Note to her trash, which takes it out of every index
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "trash_note", payload: originalHash });
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    let trash: Link[] = await alice.cells[0].callZome({ zome_name: "notes", fn_name: "list_trash", payload: null });
+    assert.equal(trash.length, 1);
+    assert.deepEqual(trash[0].target, originalHash);
+    let links: Link[] = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "get_notes_for_agent", payload: alice.agentPubKey });
+    assert.equal(links.length, 0);
+    links = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "get_notes_by_tag", payload: "work" });
+    assert.equal(links.length, 0);
+
+    // Updating the trashed Note would put it back in the indexes, so Alice has to restore it first
+    await expect(alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "update_note",
+      payload: {
+        original_note_hash: originalHash,
+        previous_note_hash: originalHash,
+        updated_note: await sampleNote(alice.cells[0], { tags: ["work"], content: "Edited in the trash" }),
+      },
+    })).rejects.toThrow();
+    const hits: any[] = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "search_notes", payload: "edited" });
+    assert.equal(hits.length, 0);
+
+    // Bob cannot trash Alice's Note
+    await expect(
+      bob.cells[0].callZome({ zome_name: "notes", fn_name: "trash_note", payload: originalHash }),
+    ).rejects.toThrow();
+
+    // Alice restores it
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "restore_note", payload: originalHash });
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    trash = await alice.cells[0].callZome({ zome_name: "notes", fn_name: "list_trash", payload: null });
+    assert.equal(trash.length, 0);
+    links = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "get_notes_for_agent", payload: alice.agentPubKey });
+    assert.equal(links.length, 1);
+    links = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "get_notes_by_tag", payload: "work" });
+    assert.equal(links.length, 1);
+
+    // Only trashed Notes can be purged
+    await expect(
+      alice.cells[0].callZome({ zome_name: "notes", fn_name: "purge_note", payload: originalHash }),
+    ).rejects.toThrow();
+
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "trash_note", payload: originalHash });
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "purge_note", payload: originalHash });
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    trash = await alice.cells[0].callZome({ zome_name: "notes", fn_name: "list_trash", payload: null });
+    assert.equal(trash.length, 0);
+    const deletes = await bob.cells[0].callZome({ zome_name: "notes", fn_name: "get_all_deletes_for_note", payload: originalHash });
+    assert.equal(deletes.length, 1);
+
+    // Nothing has been in the trash for longer than the retention period
+    const purged: ActionHash[] = await alice.cells[0].callZome({ zome_name: "notes", fn_name: "purge_expired_trash", payload: null });
+    assert.equal(purged.length, 0);
+  });
+});
+
+test("a restored Note stays in the time range it was created in", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice] = await scenario.addPlayersWithApps([appSource]);
+
+    const record: Record = await createNote(alice.cells[0], await sampleNote(alice.cells[0]));
+    const originalHash: ActionHash = record.signed_action.hashed.hash;
+    const createdAt: number = record.signed_action.hashed.content.timestamp;
+
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "trash_note", payload: originalHash });
+    await new Promise(resolve => setTimeout(resolve, 1000));
+    await alice.cells[0].callZome({ zome_name: "notes", fn_name: "restore_note", payload: originalHash });
+
+    // The range only spans the creation time of the Note, which was indexed again well after it
+    const page = await alice.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "get_notes_in_range",
+      payload: { start: createdAt, end: createdAt + 1, limit: 10, cursor: null },
+    });
+    assert.equal(page.links.length, 1);
+    assert.deepEqual(page.links[0].target, originalHash);
+  });
+});
@@ -14,7 +14,11 @@ import { CallableCell } from "@holochain/tryorama";
 export async function sampleProfile(cell: CallableCell, partialProfile = {}) {
   return {
     ...{
-      nickname: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+      nickname: "Lorem ipsum",
+      avatar: null,
+      bio: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+      timezone: "Europe/Paris",
+      fields: {},
     },
     ...partialProfile,
   };
@@ -0,0 +1,93 @@
+import { assert, test } from "vitest";
+
+import {
+  AgentPubKey,
+  AppBundleSource,
+  Link,
+  Record,
+} from "@holochain/client";
+import { CallableCell, dhtSync, runScenario } from "@holochain/tryorama";
+import { decode } from "@msgpack/msgpack";
+
+import { createProfile, sampleProfile } from "./common.js";
+
+test("search Profiles by nickname prefix", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice and Bob create their Profiles
+    const aliceRecord: Record = await createProfile(alice.cells[0], await sampleProfile(alice.cells[0], { nickname: "Alice" }));
+    await createProfile(bob.cells[0], await sampleProfile(bob.cells[0], { nickname: "Albert" }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob searches for profiles
+    let found: Link[] = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "search_profiles",
+      payload: "al",
+    });
+    assert.equal(found.length, 2);
+    found = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "search_profiles",
+      payload: "ALI",
+    });
+    assert.equal(found.length, 1);
+    assert.deepEqual(found[0].target, alice.agentPubKey);
+
+    const agents: AgentPubKey[] = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_all_agents_with_profile",
+      payload: null,
+    });
+    assert.equal(agents.length, 2);
+
+    const aliceProfile: Record = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_agent_profile",
+      payload: alice.agentPubKey,
+    });
+    assert.equal((decode((aliceProfile.entry as any).Present.entry) as any).nickname, "Alice");
+
+    // Alice renames herself
+    await alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "update_profile",
+      payload: {
+        original_profile_hash: aliceRecord.signed_action.hashed.hash,
+        previous_profile_hash: aliceRecord.signed_action.hashed.hash,
+        updated_profile: await sampleProfile(alice.cells[0], { nickname: "Zoe" }),
+      },
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    found = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "search_profiles",
+      payload: "ali",
+    });
+    assert.equal(found.length, 0);
+    found = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "search_profiles",
+      payload: "zoe",
+    });
+    assert.equal(found.length, 1);
+  });
+});
@@ -0,0 +1,81 @@
+import { assert, test } from "vitest";
+
+import {
+  AppBundleSource,
+  Record,
+} from "@holochain/client";
+import { dhtSync, pause, runScenario } from "@holochain/tryorama";
+
+import { createProfile, sampleProfile } from "./common.js";
+
+test("followers of an agent are signaled when their Profile is updated", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Bob listens to the signals of his conductor
+    const signals: any[] = [];
+    bob.appWs.on("signal", (signal: any) => {
+      const payload = signal.value?.payload ?? signal.App?.payload;
+      if (payload?.type === "ProfileUpdated") signals.push(payload);
+    });
+
+    // Alice and Bob create their Profiles
+    const aliceRecord: Record = await createProfile(alice.cells[0], await sampleProfile(alice.cells[0], { nickname: "alice" }));
+    await createProfile(bob.cells[0], await sampleProfile(bob.cells[0], { nickname: "bob" }));
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob doesn't follow Alice yet, so her first rename isn't sent to him
+    const renamed: Record = await alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "update_profile",
+      payload: {
+        original_profile_hash: aliceRecord.signed_action.hashed.hash,
+        previous_profile_hash: aliceRecord.signed_action.hashed.hash,
+        updated_profile: await sampleProfile(alice.cells[0], { nickname: "ally" }),
+      },
+    });
+
+    await pause(1000);
+    assert.equal(signals.length, 0);
+
+    // Bob follows Alice, who renames herself again
+    await bob.cells[0].callZome({
+      zome_name: "notes",
+      fn_name: "subscribe_to_author",
+      payload: alice.agentPubKey,
+    });
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    await alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "update_profile",
+      payload: {
+        original_profile_hash: aliceRecord.signed_action.hashed.hash,
+        previous_profile_hash: renamed.signed_action.hashed.hash,
+        updated_profile: await sampleProfile(alice.cells[0], { nickname: "alicia" }),
+      },
+    });
+
+    await pause(1000);
+
+    assert.equal(signals.length, 1);
+    assert.deepEqual(signals[0].agent, alice.agentPubKey);
+    assert.equal(signals[0].profile.nickname, "alicia");
+  });
+});
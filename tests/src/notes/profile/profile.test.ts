@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import {
   ActionHash,
@@ -78,3 +78,159 @@ test("create and read Profile", async () => {
     assert.deepEqual(sample, decode((createReadOutput.entry as any).Present.entry) as any);
   });
 });
+
+test("an agent can only create one Profile", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Profile, but can't create a second one
+    const record: Record = await createProfile(alice.cells[0]);
+    assert.ok(record);
+    await expect(createProfile(alice.cells[0])).rejects.toThrow();
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob can't update Alice's Profile
+    await expect(bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "update_profile",
+      payload: {
+        original_profile_hash: record.signed_action.hashed.hash,
+        previous_profile_hash: record.signed_action.hashed.hash,
+        updated_profile: await sampleProfile(bob.cells[0], { nickname: "bob" }),
+      },
+    })).rejects.toThrow();
+  });
+});
+
+test("create a Profile with an avatar and reject invalid nicknames", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice can't use a nickname with forbidden characters
+    await expect(createProfile(alice.cells[0], await sampleProfile(alice.cells[0], { nickname: "<alice>" })))
+      .rejects.toThrow();
+
+    // Alice can't upload an avatar whose content is not of the type it declares
+    await expect(alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "create_avatar",
+      payload: { mime_type: "image/png", bytes: new Uint8Array([0xff, 0xd8, 0xff, 0xe0, 0, 16, 74, 70]) },
+    })).rejects.toThrow("The content of the avatar is not of type image/png");
+    await expect(alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "create_avatar",
+      payload: { mime_type: "image/png", bytes: new TextEncoder().encode("<svg onload=alert(1)>") },
+    })).rejects.toThrow();
+
+    // Alice uploads an avatar and references it from her Profile
+    const avatar = { mime_type: "image/png", bytes: new Uint8Array([137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13]) };
+    const avatarHash = await alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "create_avatar",
+      payload: avatar,
+    });
+    const sample = await sampleProfile(alice.cells[0], { avatar: avatarHash, fields: { website: "https://example.com" } });
+    const record: Record = await createProfile(alice.cells[0], sample);
+    assert.deepEqual(sample, decode((record.entry as any).Present.entry) as any);
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob fetches Alice's avatar
+    const fetchedAvatar = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_avatar",
+      payload: avatarHash,
+    });
+    assert.deepEqual(fetchedAvatar.bytes, avatar.bytes);
+  });
+});
+
+test("update a Profile and get its latest revision", async () => {
+  await runScenario(async scenario => {
+    // Construct proper paths for your app.
+    // This assumes app bundle created by the `hc app pack` command.
+    const testAppPath = process.cwd() + "/../workdir/holonotes.happ";
+
+    // Set up the app to be installed
+    const appBundleSource: AppBundleSource = { type: "path", value: testAppPath };
+    const appSource = { appBundleSource };
+
+    // Add 2 players with the test app to the Scenario. The returned players
+    // can be destructured.
+    const [alice, bob] = await scenario.addPlayersWithApps([appSource, appSource]);
+
+    // Shortcut peer discovery through gossip and register all agents in every
+    // conductor of the scenario.
+    await scenario.shareAllAgents();
+
+    // Alice creates a Profile and updates it twice
+    const record: Record = await createProfile(alice.cells[0]);
+    const originalHash = record.signed_action.hashed.hash;
+    let previousHash = originalHash;
+    for (const nickname of ["alice", "alice.b"]) {
+      const updated: Record = await alice.cells[0].callZome({
+        zome_name: "profile",
+        fn_name: "update_profile",
+        payload: {
+          original_profile_hash: originalHash,
+          previous_profile_hash: previousHash,
+          updated_profile: await sampleProfile(alice.cells[0], { nickname }),
+        },
+      });
+      previousHash = updated.signed_action.hashed.hash;
+    }
+
+    // Alice's own profile is the newest revision
+    const myProfile: Record = await alice.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_my_profile",
+      payload: null,
+    });
+    assert.equal((decode((myProfile.entry as any).Present.entry) as any).nickname, "alice.b");
+
+    await dhtSync([alice, bob], alice.cells[0].cell_id[0]);
+
+    // Bob sees the latest revision and all the revisions
+    const latest: Record = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_latest_profile",
+      payload: originalHash,
+    });
+    assert.deepEqual(latest.signed_action.hashed.hash, previousHash);
+    const revisions: Record[] = await bob.cells[0].callZome({
+      zome_name: "profile",
+      fn_name: "get_profile_revisions",
+      payload: originalHash,
+    });
+    assert.equal(revisions.length, 3);
+  });
+});
@@ -7,14 +7,16 @@ const CreateNote: FC<CreateNoteProps> = ({ onNoteCreated }) => {
   const { client } = useContext(ClientContext);
   const [title, setTitle] = useState<string>("");
   const [content, setContent] = useState<string>("");
-  const [createdAt, setCreatedAt] = useState<number>(Date.now() * 1000);
   const [isNoteValid, setIsNoteValid] = useState(false);
 
   const createNote = async () => {
     const noteEntry: Note = {
       title: title!,
       content: content!,
-      created_at: createdAt!,
+      created_at: Date.now() * 1000,
+      tags: [],
+      editors: [],
+      format: "Plain",
     };
     try {
       const record = await client?.callZome({
@@ -26,7 +28,6 @@ const CreateNote: FC<CreateNoteProps> = ({ onNoteCreated }) => {
       onNoteCreated && onNoteCreated(record.signed_action.hashed.hash);
       setTitle(""); // Reset form
       setContent("");
-      setCreatedAt(Date.now() * 1000);
     } catch (e) {
       console.error(e);
     }
@@ -62,20 +63,6 @@ const CreateNote: FC<CreateNoteProps> = ({ onNoteCreated }) => {
         />
       </div>
 
-      <div className="form-group">
-        <label htmlFor="createdAt">Created At</label>
-        <input
-          id="createdAt"
-          name="createdAt"
-          type="datetime-local"
-          value={new Date(createdAt / 1000 - (new Date(createdAt / 1000).getTimezoneOffset() * 60000))
-            .toISOString()
-            .slice(0, 16)}
-          onChange={(e) => setCreatedAt(Math.floor(new Date(e.target.value).getTime() / 1000))}
-          required
-        />
-      </div>
-
       <button type="submit" disabled={!isNoteValid}>
         Create Note
       </button>
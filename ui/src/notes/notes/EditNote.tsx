@@ -14,14 +14,14 @@ const EditNote: FC<EditNoteProps> = ({
   const { client } = useContext(ClientContext);
   const [title, setTitle] = useState<string | undefined>(currentNote?.title);
   const [content, setContent] = useState<string | undefined>(currentNote?.content);
-  const [createdAt, setCreatedAt] = useState<number | undefined>(currentNote?.created_at);
+  const createdAt = currentNote?.created_at;
   const [isNoteValid, setIsNoteValid] = useState(false);
 
   const updateNote = useCallback(async () => {
     const note: Partial<Note> = {
+      ...currentNote,
       title,
       content,
-      created_at: createdAt,
     };
     try {
       const updateRecord = await client?.callZome({
@@ -38,7 +38,7 @@ const EditNote: FC<EditNoteProps> = ({
     } catch (e) {
       onNoteUpdateError && onNoteUpdateError(e as HolochainError);
     }
-  }, [client, currentRecord, onNoteUpdated, onNoteUpdateError, originalNoteHash, title, content, createdAt]);
+  }, [client, currentRecord, currentNote, onNoteUpdated, onNoteUpdateError, originalNoteHash, title, content]);
 
   useEffect(() => {
     if (!currentRecord) {
@@ -89,8 +89,7 @@ const EditNote: FC<EditNoteProps> = ({
                   .slice(0, 16)
               : ""
           }
-          onChange={(e) => setCreatedAt(Math.floor(new Date(e.target.value).getTime() / 1000))}
-          required
+          readOnly
         />
       </div>
       <div className="note-actions">
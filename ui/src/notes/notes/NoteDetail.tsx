@@ -3,12 +3,13 @@ import { decode } from "@msgpack/msgpack";
 import { FC, useCallback, useContext, useEffect, useState } from "react";
 import { ClientContext } from "../../ClientContext";
 import EditNote from "./EditNote";
-import type { Note } from "./types";
+import type { LatestNote, Note } from "./types";
 
 const NoteDetail: FC<NoteDetailProps> = ({ noteHash, onNoteDeleted }) => {
   const { client } = useContext(ClientContext);
   const [record, setRecord] = useState<Record | undefined>(undefined);
   const [note, setNote] = useState<Note | undefined>(undefined);
+  const [diverged, setDiverged] = useState(false);
   const [editing, setEditing] = useState(false);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<HolochainError | undefined>();
@@ -17,13 +18,14 @@ const NoteDetail: FC<NoteDetailProps> = ({ noteHash, onNoteDeleted }) => {
     setLoading(true);
     setRecord(undefined);
     try {
-      const result = await client?.callZome({
+      const result: LatestNote | undefined = await client?.callZome({
         role_name: "notes",
         zome_name: "notes",
         fn_name: "get_latest_note",
         payload: noteHash,
       });
-      setRecord(result);
+      setRecord(result?.record);
+      setDiverged(!!result?.diverged);
     } catch (e) {
       setError(e as HolochainError);
     } finally {
@@ -37,7 +39,7 @@ const NoteDetail: FC<NoteDetailProps> = ({ noteHash, onNoteDeleted }) => {
       const result = await client?.callZome({
         role_name: "notes",
         zome_name: "notes",
-        fn_name: "delete_note",
+        fn_name: "trash_note",
         payload: noteHash,
       });
       if (result) {
@@ -86,6 +88,7 @@ const NoteDetail: FC<NoteDetailProps> = ({ noteHash, onNoteDeleted }) => {
       ) : record ? (
         <>
           <h4>{note?.title}</h4>
+          {diverged && <div className="alert">This note was edited concurrently and has unmerged revisions.</div>}
           <p>{note?.content}</p>
           <div className="note-date">
             {note?.created_at ? new Date(note.created_at / 1000).toLocaleString() : ""}
@@ -93,7 +96,7 @@ const NoteDetail: FC<NoteDetailProps> = ({ noteHash, onNoteDeleted }) => {
           <div className="note-actions">
             <button onClick={() => setEditing(true)}>Edit</button>
             <button className="delete" onClick={deleteNote}>
-              Delete
+              Move to trash
             </button>
           </div>
         </>
@@ -39,11 +39,41 @@ export type NotesSignal = {
 
 /* dprint-ignore-start */
 export type EntryTypes =
- | ({  type: 'Note'; } & Note);
+ | ({  type: 'Note'; } & Note)
+ | ({  type: 'Notebook'; } & Notebook)
+ | ({  type: 'AttachmentChunk'; } & AttachmentChunk)
+ | ({  type: 'AttachmentManifest'; } & AttachmentManifest);
 /* dprint-ignore-end */
 
 export interface Note {
   title: string;
   content: string;
   created_at: number;
+  tags: string[];
+  editors: AgentPubKey[];
+  format: NoteFormat;
+}
+
+export type NoteFormat = "Plain" | "Markdown" | "RichText";
+
+export interface Notebook {
+  name: string;
+  parent: ActionHash | undefined;
+}
+
+export interface AttachmentChunk {
+  bytes: Uint8Array;
+}
+
+export interface AttachmentManifest {
+  name: string;
+  mime_type: string;
+  size: number;
+  sha256: Uint8Array;
+  chunks: EntryHash[];
+}
+
+export interface LatestNote {
+  record: Record;
+  diverged: boolean;
 }
@@ -7,11 +7,16 @@ import type { Profile } from "./types";
 const CreateProfile: FC<CreateProfileProps> = ({ onProfileCreated }) => {
   const { client } = useContext(ClientContext);
   const [nickname, setNickname] = useState<string>("");
+  const [bio, setBio] = useState<string>("");
   const [isProfileValid, setIsProfileValid] = useState(false);
 
   const createProfile = async () => {
     const profileEntry: Profile = {
       nickname: nickname!,
+      avatar: null,
+      bio,
+      timezone: Intl.DateTimeFormat().resolvedOptions().timeZone ?? null,
+      fields: {},
     };
     try {
       const record = await client?.callZome({
@@ -37,6 +42,10 @@ const CreateProfile: FC<CreateProfileProps> = ({ onProfileCreated }) => {
         <label htmlFor="Nickname">Nickname</label>
         <input type="text" name="Nickname" value={nickname} onChange={(e) => setNickname(e.target.value)} />
       </div>
+      <div>
+        <label htmlFor="Bio">Bio</label>
+        <textarea name="Bio" value={bio} onChange={(e) => setBio(e.target.value)} />
+      </div>
 
       <button disabled={!isProfileValid} onClick={() => createProfile()}>
         Create Profile
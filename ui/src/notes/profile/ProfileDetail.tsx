@@ -62,6 +62,14 @@ const ProfileDetail: FC<ProfileDetailProps> = ({ profileHash, onProfileDeleted }
               </span>
               <span>{profile?.nickname}</span>
             </div>
+            {profile?.bio && (
+              <div>
+                <span>
+                  <strong>Bio:</strong>
+                </span>
+                <span>{profile.bio}</span>
+              </div>
+            )}
             <div>
             </div>
           </section>
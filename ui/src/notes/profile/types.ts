@@ -35,13 +35,27 @@ export type ProfileSignal = {
   type: "LinkDeleted";
   action: SignedActionHashed<DeleteLink>;
   link_type: string;
+} | {
+  type: "ProfileUpdated";
+  agent: AgentPubKey;
+  profile: Profile;
 };
 
 /* dprint-ignore-start */
 export type EntryTypes =
- | ({  type: 'Profile'; } & Profile);
+ | ({  type: 'Profile'; } & Profile)
+ | ({  type: 'Avatar'; } & Avatar);
 /* dprint-ignore-end */
 
 export interface Profile {
   nickname: string;
+  avatar: EntryHash | null;
+  bio: string;
+  timezone: string | null;
+  fields: { [name: string]: string };
+}
+
+export interface Avatar {
+  mime_type: string;
+  bytes: Uint8Array;
 }